@@ -1,16 +1,19 @@
 use database::ModuleCollection;
-use path::Path;
-use std::collections::BinaryHeap;
-use types::Module;
+use path::{Planner, Query, Ranking, Report, Strategy};
+use types::{AcademicYear, Module};
 
 #[allow(unused)]
 async fn db() {
     use database::Client;
     use prereqtree::PrereqTree;
     let m = Client::debug_init().await.unwrap();
-    let top = m.find_one("CS3244", "2022/2023").await.unwrap();
+    let top =
+        m.find_one("CS3244", AcademicYear::new(2022).unwrap()).await.unwrap();
     let sample_space = m
-        .flatten_requirements(vec!["CS3244".to_string()], "2022/2023")
+        .flatten_requirements(
+            vec!["CS3244".to_string()],
+            AcademicYear::new(2022).unwrap(),
+        )
         .await
         .unwrap();
     println!("{sample_space:?}");
@@ -19,7 +22,7 @@ async fn db() {
 async fn sample_space(m: &ModuleCollection, codes: Vec<&str>) -> Vec<Module> {
     m.flatten_requirements(
         codes.iter().map(|v| v.to_string()).collect(),
-        "2022/2023",
+        AcademicYear::new(2022).unwrap(),
     )
     .await
     .unwrap()
@@ -28,53 +31,48 @@ async fn sample_space(m: &ModuleCollection, codes: Vec<&str>) -> Vec<Module> {
 #[tokio::main]
 async fn main() {
     use database::Client;
+    // rank the routes by the criteria passed in as the first argument, such
+    // as "semesters,credits,early"
+    let criteria = match std::env::args().nth(1) {
+        Some(list) => match Ranking::parse_criteria(&list) {
+            Ok(criteria) => criteria,
+            Err(e) => {
+                eprintln!("{e}");
+                std::process::exit(1);
+            }
+        },
+        None => Ranking::default_criteria(),
+    };
     let m = Client::debug_init().await.unwrap();
     let sample_space = sample_space(&m, vec!["CS3244"]).await;
 
-    // List of possible paths to take that reaches CS3244
-    let mut possible_routes = vec![];
-    let mut prl = usize::MAX;
-
-    let mut pq: BinaryHeap<Path> = BinaryHeap::new();
-    pq.push(Path::new());
     // set the modules that want to be completed
     // let want = vec!["CS3244".to_string(), "CS3216".to_string()];
     let want = vec!["CS3244".to_string()];
+    let query = Query::new(sample_space, want);
+    let query = Query { strategy: Strategy::AStar, ..query };
+    // cap the weekly workload, or balance it across semesters
+    // let query = Query { max_hours: Some(40.0), ..query };
+    // let query = Query { objective: Objective::PeakWorkload, ..query };
 
-    while let Some(mut path) = pq.pop() {
-        println!("{}", pq.len());
-        println!("{path:?}");
-        if path.len() > prl {
-            break;
-        }
-        if path.len() >= 16 {
-            continue;
-        }
-        let choices = path.choices(&sample_space);
-        if path.doing_count() < 5 && choices.len() > 0 {
-            for next_mod in choices {
-                let mut path = path.clone();
-                path.mark(next_mod);
-                if path.is_done(&want) {
-                    path.next_sem();
-                    if path.len() < prl {
-                        prl = path.len();
-                        possible_routes.clear();
-                        possible_routes.push(path);
-                    } else if path.len() == prl {
-                        possible_routes.push(path);
-                    }
-                    continue;
-                }
-                pq.push(path);
-            }
-        } else {
-            path.next_sem();
-            pq.push(path);
+    // List of possible paths to take that reaches CS3244
+    let (planner, ranking) = match (
+        Planner::new(&query),
+        Ranking::new(criteria, &query.sample_space, &query.targets),
+    ) {
+        (Ok(planner), Ok(ranking)) => (planner, ranking),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("{e}");
+            std::process::exit(1);
         }
-    }
-    possible_routes.sort_by(|a, b| b.mod_count().cmp(&a.mod_count()));
+    };
+    let search = planner.search();
+    println!("expanded {} paths", search.expanded);
+    let mut possible_routes = search.routes;
+
+    ranking.sort(&mut possible_routes);
     for i in possible_routes {
-        println!("~~~~~~~~~~~~~~~~~~~~~~~~~~\n{i:?}");
+        let report = Report::new(&i, &query.sample_space);
+        println!("~~~~~~~~~~~~~~~~~~~~~~~~~~\n{report}");
     }
 }
@@ -1,4 +1,5 @@
-use crate::ModuleCollection;
+use crate::{ModuleCollection, RequirementCollection};
+use degree::Programme;
 use types::{Module, Result};
 
 /// wrapper for the standard mongo-db database with project-specific tooling
@@ -13,13 +14,19 @@ impl Database {
         ModuleCollection::new(self.0.collection::<Module>("modules"))
     }
 
+    pub fn requirements(&self) -> RequirementCollection {
+        RequirementCollection::new(
+            self.0.collection::<Programme>("requirements"),
+        )
+    }
+
     pub fn name<'a>(&'a self) -> &'a str {
         self.0.name()
     }
 
     pub async fn first_time_setup(&self) -> Result<()> {
         // list of all collections used.
-        let collections = ["modules"];
+        let collections = ["modules", "requirements"];
         for name in collections {
             self.0.create_collection(name, None).await?;
         }
@@ -6,7 +6,7 @@ use docker::Docker;
 use std::fs;
 use std::path::PathBuf;
 use std::process::Command;
-use types::Result;
+use types::{AcademicYear, Result};
 
 const TMP_DUMP: &str = "rust_dump";
 const CONTAINER: &str = "modtree-mongo-db";
@@ -94,8 +94,8 @@ pub async fn test_schema() {
 async fn build(db: &Database) -> Result<()> {
     let mods = db.modules();
     mods.drop().await?;
-    mods.import_academic_year("2021-2022", None).await?;
-    mods.import_academic_year("2022-2023", None).await?;
+    mods.import_academic_year(AcademicYear::new(2021)?, None).await?;
+    mods.import_academic_year(AcademicYear::new(2022)?, None).await?;
     Ok(())
 }
 
@@ -1,2 +1,4 @@
 mod module_collection;
+mod requirement_collection;
 pub use module_collection::ModuleCollection;
+pub use requirement_collection::RequirementCollection;
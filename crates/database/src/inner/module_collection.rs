@@ -1,10 +1,11 @@
+use degree::Filter;
 use futures::stream::StreamExt;
 use mongodb::bson::{doc, to_document, Document};
 use mongodb::options::UpdateOptions;
 use mongodb::results::{DeleteResult, InsertManyResult, UpdateResult};
 use mongodb::Cursor;
 use std::collections::{HashMap, HashSet};
-use types::{Error, Module, Result};
+use types::{AcademicYear, Error, Module, Result};
 
 #[derive(Debug, Clone)]
 pub struct ModuleCollection(mongodb::Collection<Module>);
@@ -20,12 +21,12 @@ impl ModuleCollection {
     }
 
     /// Inserts one module that is uniquely identified by module code and
-    /// academic year.
+    /// academic year. Fails if the module's academic year can't be read.
     pub async fn insert_one(&self, module: &Module) -> Result<UpdateResult> {
         let doc = to_document(module)?;
         let query = doc! {
             "module_code": module.code(),
-            "acad_year": module.acad_year(),
+            "acad_year": module.acad_year()?.to_string(),
         };
         let opts = UpdateOptions::builder().upsert(true).build();
         Ok(self.0.update_one(query, doc! { "$set": doc }, opts).await?)
@@ -69,11 +70,11 @@ impl ModuleCollection {
     pub async fn find_one(
         &self,
         module_code: &str,
-        acad_year: &str,
+        acad_year: AcademicYear,
     ) -> Result<Module> {
         let filter = doc! {
             "module_code": module_code,
-            "acad_year": acad_year,
+            "acad_year": acad_year.to_string(),
         };
         let result = self.0.find_one(filter, None).await?;
         result.ok_or(Error::ModuleNotFound(
@@ -86,12 +87,12 @@ impl ModuleCollection {
     pub async fn find_many(
         &self,
         module_codes: &Vec<String>,
-        acad_year: &str,
+        acad_year: AcademicYear,
     ) -> Result<HashMap<String, Result<Module>>> {
         let mut task: HashSet<String> =
             HashSet::from_iter(module_codes.iter().map(|v| v.to_string()));
         let doc = doc! {
-            "acad_year": acad_year,
+            "acad_year": acad_year.to_string(),
             "module_code": { "$in": &module_codes }
         };
         let mut res = HashMap::new();
@@ -103,7 +104,7 @@ impl ModuleCollection {
             }
         }
         res.extend(task.into_iter().map(|c| {
-            (c.to_owned(), Err(Error::ModuleNotFound(c, acad_year.into())))
+            (c.to_owned(), Err(Error::ModuleNotFound(c, acad_year.to_string())))
         }));
         Ok(res)
     }
@@ -111,9 +112,79 @@ impl ModuleCollection {
     /// Finds many modules, and returns a same-sized result.
     pub async fn find_many_by_year(
         &self,
-        acad_year: &str,
+        acad_year: AcademicYear,
     ) -> Result<Cursor<Module>> {
-        Ok(self.0.find(doc! { "acad_year": acad_year }, None).await?)
+        let filter = doc! { "acad_year": acad_year.to_string() };
+        Ok(self.0.find(filter, None).await?)
+    }
+
+    /// Finds the modules of an academic year whose credits lie within a
+    /// range. Modules stored before `credits` was kept are matched on their
+    /// `module_credit` instead, and modules whose credits can't be read are
+    /// left out.
+    pub async fn find_by_credits(
+        &self,
+        acad_year: AcademicYear,
+        min: f32,
+        max: f32,
+    ) -> Result<Vec<Module>> {
+        let filter = doc! {
+            "acad_year": acad_year.to_string(),
+            "$or": [
+                {
+                    "credits.min": { "$gte": min },
+                    "credits.max": { "$lte": max },
+                },
+                // missing or null, to be checked below
+                { "credits": null },
+            ],
+        };
+        let mut modules = self.find_valid(filter).await?;
+        modules.retain(|m| credits_within(m, min, max));
+        Ok(modules)
+    }
+
+    /// Finds the modules of an academic year with a subject prefix, such as
+    /// "CS". Prefixes are matched whole, so "CS" doesn't find `CSE1010`.
+    pub async fn find_by_prefix(
+        &self,
+        acad_year: AcademicYear,
+        prefix: &str,
+    ) -> Result<Vec<Module>> {
+        self.find_valid(prefix_filter(acad_year, prefix)).await
+    }
+
+    /// Finds the modules of an academic year at a level, such as 2000 for
+    /// CS2030S and MA2001.
+    pub async fn find_by_level(
+        &self,
+        acad_year: AcademicYear,
+        level: u16,
+    ) -> Result<Vec<Module>> {
+        let levels = vec![level];
+        self.find_matching(acad_year, Filter { levels, ..Filter::default() })
+            .await
+    }
+
+    /// Finds the modules of an academic year whose codes match a filter,
+    /// such as that of a degree requirement.
+    pub async fn find_matching(
+        &self,
+        acad_year: AcademicYear,
+        filter: Filter,
+    ) -> Result<Vec<Module>> {
+        let year = doc! { "acad_year": acad_year.to_string() };
+        let mut modules = self.find_valid(year).await?;
+        modules.retain(|m| filter.matches(m.code()));
+        Ok(modules)
+    }
+
+    /// Finds the modules that match a filter, leaving out those that can't
+    /// be read.
+    async fn find_valid(&self, filter: Document) -> Result<Vec<Module>> {
+        let cursor = self.0.find(filter, None).await?;
+        let v: Vec<_> = cursor.collect().await;
+        Ok(v.into_iter().filter_map(|v| v.ok()).collect())
     }
 
     /// Deletes many modules
@@ -124,12 +195,84 @@ impl ModuleCollection {
     /// For loading a new academic year into the database.
     pub async fn import_academic_year(
         &self,
-        academic_year: &str,
+        academic_year: AcademicYear,
         limit: Option<usize>,
     ) -> Result<()> {
         let loader = fetcher::Loader::new(academic_year)?;
         let modules = loader.load_all_modules(limit).await?;
-        self.delete_many(doc! { "academic_year": academic_year }).await?;
+        self.delete_many(year_filter(academic_year)).await?;
         self.insert_many_unchecked(&modules).await.map(|_| ())
     }
 }
+
+/// Checks if a module's credits, read from `module_credit` if need be, lie
+/// within a range.
+fn credits_within(module: &Module, min: f32, max: f32) -> bool {
+    module.credits().map_or(false, |c| min <= c.min && c.max <= max)
+}
+
+/// Filter for the modules of an academic year with a subject prefix. Codes
+/// are letters then digits, so a digit after the prefix means it is whole.
+fn prefix_filter(acad_year: AcademicYear, prefix: &str) -> Document {
+    let mut pattern = String::from("^");
+    for c in prefix.chars() {
+        if !c.is_ascii_alphanumeric() {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern.push_str("[0-9]");
+    doc! {
+        "acad_year": acad_year.to_string(),
+        "module_code": { "$regex": pattern },
+    }
+}
+
+/// Filter for every module of an academic year.
+fn year_filter(acad_year: AcademicYear) -> Document {
+    doc! { "acad_year": acad_year.to_string() }
+}
+
+#[test]
+fn year_filter_test() {
+    // a year's modules are deleted by the field that they are stored under
+    let json = r#"{ "acadYear": "2022/2023", "moduleCode": "CS2040S" }"#;
+    let module =
+        Module::from(serde_json::from_str::<nusmods::Module>(json).unwrap());
+    let stored = to_document(&module).unwrap();
+    let filter = year_filter(AcademicYear::new(2022).unwrap());
+    assert!(filter.iter().all(|(k, v)| stored.get(k) == Some(v)));
+}
+
+#[test]
+fn credits_within_test() {
+    let module = |credit: &str| {
+        let json = format!(
+            r#"{{ "moduleCode": "CS2040S", "moduleCredit": "{credit}" }}"#
+        );
+        let module: nusmods::Module = serde_json::from_str(&json).unwrap();
+        // as stored before `credits` was kept
+        let mut value = serde_json::to_value(Module::from(module)).unwrap();
+        value.as_object_mut().unwrap().remove("credits");
+        serde_json::from_value::<Module>(value).unwrap()
+    };
+    assert!(credits_within(&module("4"), 4.0, 4.0));
+    assert!(!credits_within(&module("4"), 0.0, 2.0));
+    assert!(credits_within(&module("2-6"), 2.0, 6.0));
+    assert!(!credits_within(&module("2-6"), 4.0, 4.0));
+    assert!(!credits_within(&module("four"), 0.0, 8.0));
+}
+
+#[test]
+fn prefix_filter_test() {
+    let year = AcademicYear::new(2022).unwrap();
+    let pattern = |prefix: &str| {
+        let filter = prefix_filter(year, prefix);
+        assert_eq!(filter.get_str("acad_year"), Ok("2022/2023"));
+        let code = filter.get_document("module_code").unwrap();
+        code.get_str("$regex").unwrap().to_string()
+    };
+    assert_eq!(pattern("CS"), "^CS[0-9]");
+    // nothing in a prefix is read as regex syntax
+    assert_eq!(pattern("C.*"), r"^C\.\*[0-9]");
+}
@@ -0,0 +1,98 @@
+use degree::Programme;
+use futures::stream::StreamExt;
+use mongodb::bson::{doc, to_document};
+use mongodb::options::UpdateOptions;
+use mongodb::results::{DeleteResult, UpdateResult};
+use std::fs;
+use std::path::Path;
+use types::{Error, Result};
+
+/// Degree requirements of each programme, keyed by programme name and year
+/// of matriculation.
+#[derive(Debug, Clone)]
+pub struct RequirementCollection(mongodb::Collection<Programme>);
+
+impl RequirementCollection {
+    pub fn new(x: mongodb::Collection<Programme>) -> Self {
+        Self(x)
+    }
+
+    /// Gets a count of all programmes in the collection.
+    pub async fn count(&self) -> Result<u64> {
+        Ok(self.0.count_documents(None, None).await?)
+    }
+
+    /// Inserts the requirements of one programme, replacing any that already
+    /// exist for the same programme and matriculation year.
+    pub async fn insert_one(
+        &self,
+        programme: &Programme,
+    ) -> Result<UpdateResult> {
+        let doc = to_document(programme)?;
+        let query = doc! {
+            "programme": &programme.programme,
+            "matric_year": &programme.matric_year,
+        };
+        let opts = UpdateOptions::builder().upsert(true).build();
+        Ok(self.0.update_one(query, doc! { "$set": doc }, opts).await?)
+    }
+
+    /// Finds the requirements of a programme for one matriculation year.
+    pub async fn find_one(
+        &self,
+        programme: &str,
+        matric_year: &str,
+    ) -> Result<Programme> {
+        let filter = doc! {
+            "programme": programme,
+            "matric_year": matric_year,
+        };
+        let result = self.0.find_one(filter, None).await?;
+        result.ok_or(Error::RequirementsNotFound(
+            programme.to_string(),
+            matric_year.to_string(),
+        ))
+    }
+
+    /// Lists the requirements of every programme and matriculation year.
+    pub async fn list_all(&self) -> Result<Vec<Programme>> {
+        let cursor = self.0.find(None, None).await?;
+        let v: Vec<_> = cursor.collect().await;
+        let valids = v.into_iter().filter_map(|v| v.ok());
+        Ok(valids.collect())
+    }
+
+    /// Deletes the requirements of a programme for one matriculation year.
+    pub async fn delete_one(
+        &self,
+        programme: &str,
+        matric_year: &str,
+    ) -> Result<DeleteResult> {
+        let query = doc! {
+            "programme": programme,
+            "matric_year": matric_year,
+        };
+        Ok(self.0.delete_one(query, None).await?)
+    }
+
+    /// Drops collection and deletes all requirements.
+    pub async fn drop(&self) -> Result<()> {
+        Ok(self.0.drop(None).await?)
+    }
+
+    /// Imports every `.json` file in a directory as the requirements of one
+    /// programme. Returns the number of programmes imported.
+    pub async fn import_dir(&self, dir: &Path) -> Result<usize> {
+        let mut files: Vec<_> = fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok().map(|v| v.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        files.sort();
+        for file in files.iter() {
+            let programme: Programme =
+                serde_json::from_str(&fs::read_to_string(file)?)?;
+            self.insert_one(&programme).await?;
+        }
+        Ok(files.len())
+    }
+}
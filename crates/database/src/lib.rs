@@ -6,4 +6,4 @@ mod module_collection;
 pub mod dump;
 pub use client::Client;
 pub use database::Database;
-pub use inner::ModuleCollection;
+pub use inner::{ModuleCollection, RequirementCollection};
@@ -1,7 +1,7 @@
 use crate::ModuleCollection;
 use prereqtree::PrereqTree;
 use std::collections::{HashMap, HashSet};
-use types::{Module, Result};
+use types::{AcademicYear, Module, Result};
 
 impl ModuleCollection {
     /// Obtain every requirement of a list of codes until all leaf nodes are
@@ -9,13 +9,13 @@ impl ModuleCollection {
     pub async fn flatten_requirements(
         &self,
         codes: Vec<String>,
-        acad_year: &str,
+        acad_year: AcademicYear,
     ) -> Result<Vec<Module>> {
         let mut remain = codes;
         let mut result: HashSet<Module> = HashSet::new();
         let mut fetched: HashSet<String> = HashSet::new();
         while !remain.is_empty() {
-            let response = self.find_many(&remain, &acad_year).await?;
+            let response = self.find_many(&remain, acad_year).await?;
             remain = vec![];
             for (code, module) in response {
                 let prereqs = match module {
@@ -1,6 +1,6 @@
 /// These require correctly importing data from AY2021/2022 and AY2022/2023.
 use database::Client;
-use types::Result;
+use types::{AcademicYear, Result};
 use util::vec_eq;
 
 fn s_vec(v: &[&str]) -> Vec<String> {
@@ -22,7 +22,7 @@ async fn flatten_requirements_test() -> Result<()> {
         "MA2002", "MA1312", // needed by ST2334
     ]);
     let received = collection
-        .flatten_requirements(modules, "2022/2023")
+        .flatten_requirements(modules, AcademicYear::new(2022).unwrap())
         .await?
         .into_iter()
         .map(|v| v.to_code())
@@ -0,0 +1,258 @@
+use crate::{Entry, Requirement};
+use std::collections::HashSet;
+use std::fmt;
+
+/// How much of a requirement a transcript meets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Satisfied,
+    /// Some modules count towards the requirement, but not enough.
+    Partial,
+    /// No modules count towards the requirement.
+    Missing,
+}
+
+/// Result of auditing a transcript against a requirement, with one child
+/// for each child requirement.
+#[derive(Debug, Clone)]
+pub struct Audit {
+    pub name: String,
+    pub status: Status,
+    /// Modules counted towards the requirement.
+    pub counted: Vec<String>,
+    /// Total MCs of the modules counted.
+    pub credits: f32,
+    pub children: Vec<Audit>,
+}
+
+impl Audit {
+    fn branch(name: &str, status: Status, children: Vec<Audit>) -> Self {
+        let mut counted: Vec<String> =
+            children.iter().flat_map(|c| c.counted.clone()).collect();
+        counted.sort();
+        counted.dedup();
+        let credits = children.iter().map(|c| c.credits).sum();
+        Self { name: name.to_string(), status, counted, credits, children }
+    }
+
+    fn fmt_indent(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        depth: usize,
+    ) -> fmt::Result {
+        let mark = match self.status {
+            Status::Satisfied => "[x]",
+            Status::Partial => "[~]",
+            Status::Missing => "[ ]",
+        };
+        let indent = "  ".repeat(depth);
+        writeln!(f, "{indent}{mark} {} ({} MCs)", self.name, self.credits)?;
+        for child in &self.children {
+            child.fmt_indent(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Audit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_indent(f, 0)
+    }
+}
+
+/// Status of a requirement that is not met, from the status of its children.
+fn progress(children: &[Audit]) -> Status {
+    match children.iter().any(|c| c.status != Status::Missing) {
+        true => Status::Partial,
+        false => Status::Missing,
+    }
+}
+
+impl Requirement {
+    /// Audits a transcript against the requirement.
+    ///
+    /// Modules are counted in transcript order, and requirements are checked
+    /// in the order that they are written. Each module counts towards only
+    /// one requirement, unless a requirement allows double counting. Under
+    /// `Any`, only the first `n` children that are met use up modules.
+    pub fn audit(&self, transcript: &[Entry]) -> Audit {
+        self.audit_with(transcript, &mut HashSet::new())
+    }
+
+    /// Audits a transcript, where `used` holds the modules that already
+    /// count towards another requirement.
+    fn audit_with(
+        &self,
+        transcript: &[Entry],
+        used: &mut HashSet<String>,
+    ) -> Audit {
+        use Status::*;
+        match self {
+            Self::All { name, all } => {
+                let children: Vec<_> = all
+                    .iter()
+                    .map(|r| r.audit_with(transcript, used))
+                    .collect();
+                let status =
+                    match children.iter().all(|c| c.status == Satisfied) {
+                        true => Satisfied,
+                        false => progress(&children),
+                    };
+                Audit::branch(name, status, children)
+            }
+            Self::Any { name, n, any } => {
+                let (mut children, mut met) = (vec![], 0);
+                for r in any {
+                    let mut trial = used.clone();
+                    let audit = r.audit_with(transcript, &mut trial);
+                    if met < *n && audit.status == Satisfied {
+                        *used = trial;
+                        met += 1;
+                    }
+                    children.push(audit);
+                }
+                let status = match met >= *n {
+                    true => Satisfied,
+                    false => progress(&children),
+                };
+                Audit::branch(name, status, children)
+            }
+            Self::Modules { name, modules, count, credits, double_count } => {
+                let (mut counted, mut total) = (vec![], 0.0);
+                let met = |done: &[String], mcs: f32| match (count, credits) {
+                    (None, None) => {
+                        modules.codes.iter().all(|c| done.contains(c))
+                    }
+                    _ => {
+                        count.is_none_or(|n| done.len() >= n)
+                            && credits.is_none_or(|c| mcs >= c)
+                    }
+                };
+                for entry in transcript {
+                    if met(&counted, total) {
+                        break;
+                    }
+                    let code = &entry.code;
+                    if !modules.matches(code)
+                        || counted.contains(code)
+                        || (!double_count && used.contains(code))
+                    {
+                        continue;
+                    }
+                    counted.push(code.to_string());
+                    total += entry.credits;
+                }
+                if !double_count {
+                    used.extend(counted.iter().cloned());
+                }
+                let status = match (met(&counted, total), counted.is_empty()) {
+                    (true, _) => Satisfied,
+                    (false, false) => Partial,
+                    (false, true) => Missing,
+                };
+                let name = name.to_string();
+                Audit {
+                    name,
+                    status,
+                    counted,
+                    credits: total,
+                    children: vec![],
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn audit_test() {
+    let degree: Requirement = serde_json::from_str(
+        r#"{
+            "name": "Computer Science",
+            "all": [
+                { "name": "Core", "modules": { "codes": ["CS1101S", "CS2030S"] } },
+                {
+                    "name": "Level-4000",
+                    "modules": {
+                        "prefixes": ["CS"],
+                        "levels": [4000],
+                        "exclude": ["CS4000"]
+                    },
+                    "credits": 8
+                },
+                {
+                    "name": "GE",
+                    "n": 2,
+                    "any": [
+                        { "name": "GEA", "modules": { "prefixes": ["GEA"] }, "count": 1 },
+                        { "name": "GEC", "modules": { "prefixes": ["GEC"] }, "count": 1 },
+                        { "name": "GEN", "modules": { "prefixes": ["GEN"] }, "count": 1 }
+                    ]
+                }
+            ]
+        }"#,
+    )
+    .unwrap();
+    let json = serde_json::to_string(&degree).unwrap();
+    assert_eq!(serde_json::from_str::<Requirement>(&json).unwrap(), degree);
+
+    let transcript = [
+        Entry::new("CS1101S", 4.0),
+        Entry::new("CS4000", 4.0),
+        Entry::new("CS4248", 4.0),
+        Entry::new("GEA1000", 4.0),
+    ];
+    let audit = degree.audit(&transcript);
+    let status: Vec<_> = audit.children.iter().map(|c| c.status).collect();
+    use Status::*;
+    assert_eq!(status, [Partial, Partial, Partial]);
+    assert_eq!(audit.status, Partial);
+    assert_eq!(audit.children[1].counted, ["CS4248"]);
+
+    let transcript = [
+        &transcript[..],
+        &[
+            Entry::new("CS2030S", 4.0),
+            Entry::new("CS4231", 4.0),
+            Entry::new("GEN2000", 4.0),
+        ],
+    ]
+    .concat();
+    let audit = degree.audit(&transcript);
+    assert_eq!(audit.status, Satisfied);
+    assert_eq!(audit.credits, 24.0);
+    assert_eq!(audit.children[2].children[1].status, Missing);
+}
+
+#[test]
+fn double_count_test() {
+    let requirement = |double_count| Requirement::All {
+        name: "Degree".to_string(),
+        all: vec![
+            Requirement::Modules {
+                name: "Major".to_string(),
+                modules: crate::Filter {
+                    prefixes: vec!["MA".to_string()],
+                    ..Default::default()
+                },
+                count: Some(1),
+                credits: None,
+                double_count: false,
+            },
+            Requirement::Modules {
+                name: "Minor".to_string(),
+                modules: crate::Filter {
+                    codes: vec!["MA1521".to_string()],
+                    ..Default::default()
+                },
+                count: None,
+                credits: None,
+                double_count,
+            },
+        ],
+    };
+    let transcript = [Entry::new("MA1521", 4.0)];
+    let audit = requirement(false).audit(&transcript);
+    assert_eq!(audit.children[1].status, Status::Missing);
+    let audit = requirement(true).audit(&transcript);
+    assert_eq!(audit.status, Status::Satisfied);
+}
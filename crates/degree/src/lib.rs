@@ -0,0 +1,173 @@
+mod audit;
+
+pub use audit::{Audit, Status};
+
+use serde::{Deserialize, Serialize};
+use types::{Module, ModuleCode};
+
+/// A degree requirement, as a tree of smaller requirements. Like
+/// `PrereqTree`, it is written in JSON without tags:
+///
+/// ```json
+/// { "name": "Core", "all": [...] }
+/// { "name": "GE", "n": 1, "any": [...] }
+/// { "name": "CS4K", "modules": { "prefixes": ["CS"] }, "credits": 40 }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum Requirement {
+    /// Every child requirement must be met.
+    All { name: String, all: Vec<Requirement> },
+    /// At least `n` of the child requirements must be met.
+    Any { name: String, n: usize, any: Vec<Requirement> },
+    /// Enough modules that match a filter must be taken: at least `count`
+    /// modules and at least `credits` MCs. If neither is given, every module
+    /// listed in the filter's `codes` must be taken.
+    Modules {
+        name: String,
+        modules: Filter,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        count: Option<usize>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        credits: Option<f32>,
+        /// Whether modules already counted towards another requirement may
+        /// also count towards this one. Modules counted here are then not
+        /// used up.
+        #[serde(default)]
+        double_count: bool,
+    },
+}
+
+/// Requirements of a degree programme, for students who matriculated in a
+/// given academic year.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Programme {
+    pub programme: String,
+    /// Academic year of matriculation, such as "2022/2023".
+    pub matric_year: String,
+    pub requirement: Requirement,
+}
+
+/// Picks out modules by code. A module matches if it is listed in `codes`,
+/// or if it matches one of the `prefixes` and one of the `levels`, where an
+/// empty list matches everything. Modules in `exclude` never match.
+///
+/// Prefixes and levels are checked against the parsed `ModuleCode`, so the
+/// prefix "CS" doesn't match `CSE1010`, and a code that can't be parsed only
+/// matches through `codes`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Filter {
+    #[serde(default)]
+    pub codes: Vec<String>,
+    #[serde(default)]
+    pub prefixes: Vec<String>,
+    /// Module levels, such as 4000 for `CS4248`.
+    #[serde(default)]
+    pub levels: Vec<u16>,
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+impl Filter {
+    pub fn matches(&self, code: &str) -> bool {
+        if self.exclude.iter().any(|v| v == code) {
+            return false;
+        }
+        if self.codes.iter().any(|v| v == code) {
+            return true;
+        }
+        if self.prefixes.is_empty() && self.levels.is_empty() {
+            return false;
+        }
+        let Ok(code) = code.parse::<ModuleCode>() else {
+            return false;
+        };
+        let prefix = self.prefixes.is_empty()
+            || self.prefixes.iter().any(|p| p == code.prefix());
+        let level =
+            self.levels.is_empty() || self.levels.contains(&code.level());
+        prefix && level
+    }
+}
+
+/// One module on a transcript.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub code: String,
+    pub credits: f32,
+}
+
+impl Entry {
+    pub fn new(code: &str, credits: f32) -> Self {
+        Self { code: code.to_string(), credits }
+    }
+}
+
+/// Counts a module at its fewest credits. Fails if its credits can't be
+/// read, so that they are not counted as none.
+impl TryFrom<&Module> for Entry {
+    type Error = types::Error;
+
+    fn try_from(m: &Module) -> types::Result<Self> {
+        Ok(Self { code: m.to_code(), credits: m.credits()?.min })
+    }
+}
+
+impl Requirement {
+    /// Gets the requirement's name.
+    pub fn name(&self) -> &String {
+        match self {
+            Self::All { name, .. } => name,
+            Self::Any { name, .. } => name,
+            Self::Modules { name, .. } => name,
+        }
+    }
+}
+
+#[test]
+fn requirement_files_test() {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../../requirements");
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            let json = std::fs::read_to_string(&path).unwrap();
+            let parsed = serde_json::from_str::<Programme>(&json);
+            assert!(parsed.is_ok(), "{path:?}: {parsed:?}");
+        }
+    }
+}
+
+#[test]
+fn entry_test() {
+    let module = |credits: &str| {
+        let json = format!(
+            r#"{{ "moduleCode": "CS3203", "moduleCredit": "{credits}" }}"#
+        );
+        Module::from(serde_json::from_str::<nusmods::Module>(&json).unwrap())
+    };
+    let entry = Entry::try_from(&module("8")).unwrap();
+    assert_eq!(entry, Entry::new("CS3203", 8.0));
+    assert!(Entry::try_from(&module("")).is_err());
+}
+
+#[test]
+fn filter_test() {
+    let filter = |prefixes: &[&str], levels: &[u16]| Filter {
+        prefixes: prefixes.iter().map(|p| p.to_string()).collect(),
+        levels: levels.to_vec(),
+        ..Default::default()
+    };
+    let cs = filter(&["CS"], &[]);
+    assert!(cs.matches("CS2030S") && cs.matches("CS1010"));
+    assert!(!cs.matches("CSE1010") && !cs.matches("MA1521"));
+    let level = filter(&[], &[2000]);
+    assert!(level.matches("CS2030S") && level.matches("MA2001"));
+    assert!(!level.matches("CS1010") && !level.matches("GESS2000X0"));
+    let both = filter(&["CS", "MA"], &[1000]);
+    assert!(both.matches("CS1010") && both.matches("MA1521"));
+    assert!(!both.matches("CS2030S") && !both.matches("GEA1000"));
+    // only listed codes match if they can't be parsed
+    assert!(!filter(&["CS"], &[]).matches("CS"));
+    let listed = Filter { codes: vec!["CS".to_string()], ..cs };
+    assert!(listed.matches("CS"));
+}
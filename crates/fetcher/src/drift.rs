@@ -0,0 +1,330 @@
+use serde::de::value::BorrowedStrDeserializer;
+use serde::de::{DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::{forward_to_deserialize_any, Serialize};
+use serde_json::Value;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+use types::{AcademicYear, Credits};
+
+/// One way in which a module's JSON differs from the schema of
+/// `nusmods::Module`. Paths are written like `semesterData[0].examDate`.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Drift {
+    /// A field that is not in the schema, and so is dropped.
+    UnknownField { path: String },
+    /// A value that `nusmods` can't read, which makes the whole module
+    /// unreadable.
+    Unreadable { path: String, error: String },
+    /// A value that `nusmods` reads, but that still can't be understood,
+    /// such as credits of "four".
+    BadValue { path: String, value: String },
+}
+
+/// Every schema drift found while loading an academic year, by module code.
+#[derive(Serialize, Debug, Clone)]
+pub struct DriftReport {
+    pub academic_year: AcademicYear,
+    pub modules: BTreeMap<String, Vec<Drift>>,
+}
+
+impl DriftReport {
+    /// Number of drifts found across every module.
+    pub fn count(&self) -> usize {
+        self.modules.values().map(|v| v.len()).sum()
+    }
+}
+
+/// Reads a module from JSON with the same `Deserialize` impl that loading
+/// always uses, and notes every drift from the schema on the way. Since the
+/// check is the deserialisation itself, it can't fall out of step with the
+/// `nusmods` types: a field counts as unknown exactly when serde drops it,
+/// and `null` is fine exactly where an `Option` is expected.
+///
+/// Reading stops at the first value that can't be read, so the module is
+/// then read again without that value, until every such value is found.
+///
+/// Unknown fields inside untagged enums, such as the weeks of a lesson, are
+/// dropped by serde without being seen, so they are not reported.
+pub fn read_module(
+    value: &Value,
+) -> (serde_json::Result<nusmods::Module>, Vec<Drift>) {
+    let mut notes = Notes::default();
+    let module = read(PhantomData, value, String::new(), &notes);
+    let mut drifts = notes.drifts.take();
+    let mut unreadable = vec![];
+    while let Some(i) = drifts.iter().position(Drift::is_unreadable) {
+        let Drift::Unreadable { path, error } = drifts.remove(i) else {
+            unreachable!()
+        };
+        // leaving out a value can make the value around it unreadable, such
+        // as for a missing field, which says nothing new
+        if !notes.skip.iter().any(|p| is_inside(p, &path)) {
+            let (path, error) = (path.clone(), error);
+            unreadable.push(Drift::Unreadable { path, error });
+        }
+        if path.is_empty() {
+            break;
+        }
+        notes.skip.push(path);
+        let module = PhantomData::<nusmods::Module>;
+        let _ = read(module, value, String::new(), &notes);
+        drifts = notes.drifts.take();
+    }
+    drifts.extend(unreadable);
+    if let Some(Value::String(credits)) = value.get("moduleCredit") {
+        if Credits::parse(credits).is_err() {
+            let (path, value) = ("moduleCredit".into(), credits.to_string());
+            drifts.push(Drift::BadValue { path, value });
+        }
+    }
+    (module, drifts)
+}
+
+impl Drift {
+    fn is_unreadable(&self) -> bool {
+        matches!(self, Drift::Unreadable { .. })
+    }
+}
+
+/// Checks if the value at `inner` lies inside the value at `path`.
+fn is_inside(inner: &str, path: &str) -> bool {
+    match inner.strip_prefix(path) {
+        Some(rest) if path.is_empty() => !rest.is_empty(),
+        Some(rest) => rest.starts_with(['.', '[']),
+        None => false,
+    }
+}
+
+/// What one reading of a module has found, and the values it leaves out.
+#[derive(Default)]
+struct Notes {
+    drifts: RefCell<Vec<Drift>>,
+    /// Paths of values already found to be unreadable, read as if absent.
+    skip: Vec<String>,
+}
+
+/// Reads a value at `path`. If it can't be read, and nothing inside it was
+/// already found to be unreadable, the value is noted as unreadable.
+fn read<'de, S: DeserializeSeed<'de>>(
+    seed: S,
+    value: &'de Value,
+    path: String,
+    notes: &Notes,
+) -> serde_json::Result<S::Value> {
+    let tracked = Tracked { value, path: path.clone(), notes };
+    seed.deserialize(tracked).inspect_err(|e| {
+        let mut drifts = notes.drifts.borrow_mut();
+        if !drifts.iter().any(|d| d.is_unreadable()) {
+            drifts.push(Drift::Unreadable { path, error: e.to_string() });
+        }
+    })
+}
+
+/// A JSON value that notes the fields that are dropped while reading it.
+struct Tracked<'de, 'a> {
+    value: &'de Value,
+    path: String,
+    notes: &'a Notes,
+}
+
+impl<'de> Deserializer<'de> for Tracked<'de, '_> {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> serde_json::Result<V::Value> {
+        let (path, notes) = (self.path, self.notes);
+        match self.value {
+            Value::Array(values) => {
+                let values = values.iter().enumerate();
+                visitor.visit_seq(Seq { values, path, notes })
+            }
+            Value::Object(map) => {
+                let (fields, next) = (map.iter(), None);
+                visitor.visit_map(Map { fields, next, path, notes })
+            }
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> serde_json::Result<V::Value> {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    /// Only called for fields that the type being read doesn't have.
+    fn deserialize_ignored_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> serde_json::Result<V::Value> {
+        let path = self.path;
+        self.notes.drifts.borrow_mut().push(Drift::UnknownField { path });
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple tuple_struct
+        map struct enum identifier
+    }
+}
+
+struct Seq<'de, 'a> {
+    values: std::iter::Enumerate<std::slice::Iter<'de, Value>>,
+    path: String,
+    notes: &'a Notes,
+}
+
+impl<'de> SeqAccess<'de> for Seq<'de, '_> {
+    type Error = serde_json::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> serde_json::Result<Option<T::Value>> {
+        for (i, value) in self.values.by_ref() {
+            let path = format!("{}[{i}]", self.path);
+            if !self.notes.skip.contains(&path) {
+                return read(seed, value, path, self.notes).map(Some);
+            }
+        }
+        Ok(None)
+    }
+}
+
+struct Map<'de, 'a> {
+    fields: serde_json::map::Iter<'de>,
+    /// Path of the field whose key was just read, and its value to be read
+    /// next.
+    next: Option<(String, &'de Value)>,
+    path: String,
+    notes: &'a Notes,
+}
+
+impl<'de> MapAccess<'de> for Map<'de, '_> {
+    type Error = serde_json::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> serde_json::Result<Option<K::Value>> {
+        for (key, value) in self.fields.by_ref() {
+            let path = match self.path.as_str() {
+                "" => key.to_string(),
+                path => format!("{path}.{key}"),
+            };
+            if !self.notes.skip.contains(&path) {
+                self.next = Some((path, value));
+                let key = BorrowedStrDeserializer::new(key);
+                return seed.deserialize(key).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> serde_json::Result<V::Value> {
+        let (path, value) = self.next.take().expect("key is read first");
+        read(seed, value, path, self.notes)
+    }
+}
+
+#[test]
+fn drift_test() {
+    let json = r#"{
+        "moduleCode": "CS2040S",
+        "moduleCredit": "four",
+        "gradingBasisDescription": null,
+        "semesterData": [{
+            "semester": 1,
+            "examDate": null,
+            "examVenue": "MPSH1"
+        }],
+        "workload": [2, 1, 1, 3, 3]
+    }"#;
+    let (module, drifts) = read_module(&serde_json::from_str(json).unwrap());
+    // null is fine wherever the schema has an `Option`
+    assert!(module.is_ok());
+    assert_eq!(
+        drifts,
+        [
+            Drift::UnknownField {
+                path: "semesterData[0].examVenue".to_string(),
+            },
+            Drift::BadValue {
+                path: "moduleCredit".to_string(),
+                value: "four".to_string(),
+            },
+        ]
+    );
+
+    let json = r#"{
+        "prereqTree": { "and": ["CS1231S", { "or": ["CS1010S", "CS1101S"] }] },
+        "attributes": { "su": true },
+        "semesterData": [{
+            "semester": 2,
+            "timetable": [{
+                "classNo": "1",
+                "weeks": { "start": "2023-01-16", "end": "2023-04-14" }
+            }]
+        }]
+    }"#;
+    let (module, drifts) = read_module(&serde_json::from_str(json).unwrap());
+    assert!(module.is_ok() && drifts.is_empty());
+
+    // the innermost value that can't be read is reported
+    let unreadable = |json: &str| {
+        let (module, drifts) =
+            read_module(&serde_json::from_str(json).unwrap());
+        assert!(module.is_err());
+        match &drifts[..] {
+            [Drift::Unreadable { path, .. }] => path.to_string(),
+            drifts => panic!("{drifts:?}"),
+        }
+    };
+    assert_eq!(unreadable(r#"{ "moduleCredit": 4 }"#), "moduleCredit");
+    let json =
+        r#"{ "semesterData": [{ "semester": 1, "examDuration": "2h" }] }"#;
+    assert_eq!(unreadable(json), "semesterData[0].examDuration");
+    let json = r#"{ "prereqTree": { "nOf": [1, ["CS1010S", "CS1101S"]] } }"#;
+    assert_eq!(unreadable(json), "prereqTree");
+
+    // every value that can't be read is reported, and not the values that
+    // are unreadable only because of them
+    let json = r#"{
+        "moduleCredit": 4,
+        "semesterData": [
+            { "semester": "one" },
+            { "semester": 2, "examDuration": "2h", "examVenue": "MPSH1" }
+        ]
+    }"#;
+    let (module, drifts) = read_module(&serde_json::from_str(json).unwrap());
+    assert!(module.is_err());
+    let paths: Vec<_> = drifts
+        .iter()
+        .map(|d| match d {
+            Drift::UnknownField { path } => format!("unknown {path}"),
+            Drift::Unreadable { path, .. } => path.to_string(),
+            d => panic!("{d:?}"),
+        })
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            "unknown semesterData[1].examVenue",
+            "moduleCredit",
+            "semesterData[0].semester",
+            "semesterData[1].examDuration",
+        ]
+    );
+}
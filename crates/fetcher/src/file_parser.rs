@@ -1,4 +1,3 @@
-use crate::util;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use std::fs::{self, File};
@@ -6,7 +5,7 @@ use std::io::Write;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
-use types::Result;
+use types::{AcademicYear, Result};
 
 const NUSMODS_API_URL: &str = "https://api.nusmods.com/v2";
 
@@ -19,13 +18,13 @@ pub struct FileParser {
 }
 
 impl FileParser {
-    pub fn new(academic_year: &str) -> Result<Self> {
+    pub fn new(academic_year: AcademicYear) -> Result<Self> {
         dotenv::dotenv().expect(".env file not found");
         let root = std::env::var("MODTREE_CACHE_DIR").map(PathBuf::from)?;
         if root.is_relative() {
             Err(types::Error::RequiresAbsolutePath(root.clone()))?
         }
-        let academic_year = util::validate_academic_year(academic_year, '-')?;
+        let academic_year = academic_year.to_url();
         let base_url = PathBuf::from(NUSMODS_API_URL);
         fs::create_dir_all(&root)?;
         Ok(Self {
@@ -69,6 +68,14 @@ impl FileParser {
         self.local::<T>(&path)
     }
 
+    /// Writes text to a file in the cache, and returns its path.
+    pub fn write(&self, name: &str, text: &str) -> Result<PathBuf> {
+        let path = self.root.join(name);
+        fs::create_dir_all(&self.root)?;
+        fs::write(&path, text)?;
+        Ok(path)
+    }
+
     pub fn source_counts(&self) -> [usize; 2] {
         [
             self.local_count.load(Ordering::Relaxed),
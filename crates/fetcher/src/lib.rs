@@ -1,4 +1,5 @@
+mod drift;
 mod loader;
-mod util;
 mod file_parser;
-pub use loader::Loader;
+pub use drift::{Drift, DriftReport};
+pub use loader::{Bulk, Loader};
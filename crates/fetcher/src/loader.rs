@@ -1,28 +1,90 @@
+use crate::drift::{self, DriftReport};
 use crate::file_parser::FileParser;
 use futures::prelude::*;
 use std::collections::HashMap;
-use types::{Error, Module, Result};
+use std::sync::Mutex;
+use types::{AcademicYear, Error, Module, Result, Semester};
+
+/// Files from NUSMods that hold every module of an academic year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bulk {
+    /// `moduleInfo.json`, which is deprecated but still published.
+    ModuleInfo,
+    /// `moduleInformation.json`.
+    ModuleInformation,
+}
+
+impl Bulk {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::ModuleInfo => "moduleInfo.json",
+            Self::ModuleInformation => "moduleInformation.json",
+        }
+    }
+}
 
 /// The spirit of this loader is to always do a two-step fetch:
 ///   1. from local cache
 ///   2. from remote data (only when step 1 fails)
 #[derive(Debug)]
-pub struct Loader(FileParser);
+pub struct Loader {
+    parser: FileParser,
+    /// Check every module against the schema that `nusmods` expects.
+    strict: bool,
+    drift: Mutex<DriftReport>,
+}
 
 impl Loader {
-    pub fn new(academic_year: &str) -> Result<Self> {
-        Ok(Self(FileParser::new(academic_year)?))
+    pub fn new(academic_year: AcademicYear) -> Result<Self> {
+        let drift = DriftReport { academic_year, modules: Default::default() };
+        Ok(Self {
+            parser: FileParser::new(academic_year)?,
+            strict: false,
+            drift: Mutex::new(drift),
+        })
+    }
+
+    /// Turns on strict loading, which reports unknown fields, unreadable
+    /// values and bad credits in each module. The report is written to
+    /// `drift.json` in the academic year's cache directory at the end of
+    /// `load_all_modules`.
+    pub fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    /// Schema drift found so far in strict mode.
+    pub fn drift_report(&self) -> DriftReport {
+        self.drift.lock().unwrap().clone()
     }
 
     /// Loads one module and all of its information.
     pub async fn load_module(&self, code: &str) -> Result<nusmods::Module> {
-        Ok(self.0.load(&format!("modules/{code}.json")).await?)
+        let url = format!("modules/{code}.json");
+        if !self.strict {
+            return self.parser.load(&url).await;
+        }
+        self.read_strict(code, self.parser.load(&url).await?)
+    }
+
+    /// Reads a module from JSON, recording any schema drift in it.
+    fn read_strict(
+        &self,
+        code: &str,
+        value: serde_json::Value,
+    ) -> Result<nusmods::Module> {
+        let (module, drifts) = drift::read_module(&value);
+        if !drifts.is_empty() {
+            let mut report = self.drift.lock().unwrap();
+            report.modules.insert(code.to_string(), drifts);
+        }
+        Ok(module?)
     }
 
     /// Load list of modules from NUSMods. This pulls an extremely minimal list of modules that
     /// only contains module code, title, and semesters offered.
     pub async fn load_module_list(&self) -> Result<Vec<nusmods::ModuleShort>> {
-        Ok(self.0.load("moduleList.json").await?)
+        Ok(self.parser.load("moduleList.json").await?)
     }
 
     /// Loads all full-info modules.
@@ -50,18 +112,67 @@ impl Loader {
             attempts += 1;
             eprintln!("[fetch] retry: {attempts}");
         }
+        self.write_drift_report()?;
         if !errors.is_empty() {
             panic!("\nLoad failed after 5 attempts:\n\n{errors:?}\n\n")
         }
         task.is_empty().then_some(result).ok_or(Error::UnableToLoadAllModules)
     }
 
+    /// Loads all full-info modules from one bulk file, in a single request.
+    /// Semesters offered are merged from the module list, just as in
+    /// `load_all_modules`.
+    pub async fn load_bulk_modules(
+        &self,
+        file: Bulk,
+        limit: Option<usize>,
+    ) -> Result<Vec<Module>> {
+        let values: Vec<serde_json::Value> =
+            self.parser.load(file.name()).await?;
+        let shorts = self.load_module_list().await?;
+        let shorts: HashMap<String, nusmods::ModuleShort> =
+            shorts.into_iter().map(|v| (v.to_code(), v)).collect();
+        let limit = limit.unwrap_or(values.len());
+        println!("reading {} modules from {}.", limit, file.name());
+        let mut result = vec![];
+        let mut errors = HashMap::new();
+        for value in values.into_iter().take(limit) {
+            let code = value["moduleCode"].as_str().unwrap_or("").to_string();
+            let module = match self.strict {
+                true => self.read_strict(&code, value),
+                false => serde_json::from_value(value).map_err(Error::from),
+            };
+            let module =
+                module.and_then(|m| with_semesters(m, shorts.get(&code)));
+            match module {
+                Ok(m) => result.push(m),
+                Err(e) => _ = errors.insert(code, e),
+            }
+        }
+        self.write_drift_report()?;
+        if !errors.is_empty() {
+            Err(Error::InvalidData(format!("{}: {errors:?}", file.name())))?
+        }
+        Ok(result)
+    }
+
+    /// Writes the drift report to the cache, if loading strictly.
+    fn write_drift_report(&self) -> Result<()> {
+        if self.strict {
+            let report = self.drift_report();
+            let json = serde_json::to_string_pretty(&report)?;
+            let path = self.parser.write("drift.json", &json)?;
+            eprintln!("[fetch] {} drift(s), see {path:?}", report.count());
+        }
+        Ok(())
+    }
+
     /// Tries to load modules given a list of module codes.
     async fn try_load_modules(
         &self,
         codes: &mut HashMap<String, nusmods::ModuleShort>,
     ) -> (Vec<Module>, HashMap<String, Result<Module>>) {
-        self.0.clear_source_counts();
+        self.parser.clear_source_counts();
         let (total, mut done) = (codes.len(), 0);
         let interval = 200.max(total / 20);
         println!("fetching {} modules.", total);
@@ -71,19 +182,16 @@ impl Loader {
                 done += 1;
                 match done {
                     x if x % interval != 0 && x != total => (),
-                    x => println!("done: {x} {:?}", self.0.source_counts()),
+                    x => {
+                        println!("done: {x} {:?}", self.parser.source_counts())
+                    }
                 }
             })
             .map(|(code, short)| async move {
-                let module = self.load_module(code).await.and_then(|m| {
-                    let mut m = Module::from(m);
-                    if short.semesters.iter().all(|v| 1 <= *v && *v <= 4) {
-                        m.set_semesters(short.semesters.clone());
-                        Ok(m)
-                    } else {
-                        Err(Error::InvalidSemesters(short.semesters.clone()))
-                    }
-                });
+                let module = self
+                    .load_module(code)
+                    .await
+                    .and_then(|m| with_semesters(m, Some(short)));
                 (code.to_owned(), module)
             });
         let results = futures::stream::iter(handles)
@@ -102,3 +210,28 @@ impl Loader {
         (ok, bad)
     }
 }
+
+/// Converts a module, with the semesters that it is offered in. These are
+/// taken from the module list, or from the module's own semester data if it
+/// is not in the list.
+fn with_semesters(
+    m: nusmods::Module,
+    short: Option<&nusmods::ModuleShort>,
+) -> Result<Module> {
+    let semesters: Vec<usize> = match short {
+        Some(short) => short.semesters.clone(),
+        None => m.semester_data.iter().map(|s| s.semester).collect(),
+    };
+    let Ok(sems) = semesters.iter().map(|v| Semester::new(*v)).collect() else {
+        return Err(Error::InvalidSemesters(semesters));
+    };
+    let mut m = Module::from(m);
+    if let Err(e) = m.credits() {
+        eprintln!("[fetch] {e}");
+    }
+    if let Err(e) = m.acad_year() {
+        eprintln!("[fetch] {e}");
+    }
+    m.set_semesters(sems);
+    Ok(m)
+}
@@ -1,14 +1,23 @@
+mod drift;
 mod loader;
 mod file_parser;
-mod util;
-use loader::Loader;
-use types::Result;
+use loader::{Bulk, Loader};
+use types::{AcademicYear, Result};
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // let loader = Loader::new("2021-2022")?;
-    let loader = Loader::new("2022-2023")?;
-    let results = loader.load_all_modules(None).await?;
+    // let loader = Loader::new(AcademicYear::new(2021)?)?;
+    let loader = Loader::new(AcademicYear::new(2022)?)?.strict();
+    // reads every module from one bulk file instead of one file per module
+    let bulk = std::env::args().find_map(|v| match v.as_str() {
+        "--module-info" => Some(Bulk::ModuleInfo),
+        "--module-information" => Some(Bulk::ModuleInformation),
+        _ => None,
+    });
+    let results = match bulk {
+        Some(bulk) => loader.load_bulk_modules(bulk, None).await?,
+        None => loader.load_all_modules(None).await?,
+    };
     let count = results.len();
     println!("loaded count: {count}");
     Ok(())
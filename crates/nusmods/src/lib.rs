@@ -19,6 +19,106 @@ pub enum Workload {
     Numbers(Vec<f32>),
 }
 
+/// Weeks of the semester that a lesson runs in, either as week numbers or
+/// as a range of dates.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum Weeks {
+    Numbers(Vec<usize>),
+    Range {
+        start: String,
+        end: String,
+        #[serde(default, alias = "weekInterval")]
+        week_interval: Option<usize>,
+        /// Weeks counted from `start`, if the lesson skips some of them.
+        #[serde(default)]
+        weeks: Option<Vec<usize>>,
+    },
+}
+
+impl Default for Weeks {
+    fn default() -> Self {
+        Self::Numbers(vec![])
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Lesson {
+    #[serde(default, alias = "classNo")]
+    pub class_no: String,
+    #[serde(default, alias = "lessonType")]
+    pub lesson_type: String,
+    #[serde(default)]
+    pub day: String,
+    /// Time of day, such as "1000".
+    #[serde(default, alias = "startTime")]
+    pub start_time: String,
+    #[serde(default, alias = "endTime")]
+    pub end_time: String,
+    #[serde(default)]
+    pub weeks: Weeks,
+    #[serde(default)]
+    pub venue: String,
+    #[serde(default)]
+    pub size: Option<u32>,
+    #[serde(default, alias = "covidZone")]
+    pub covid_zone: Option<String>,
+}
+
+/// Offering of a module in one semester.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SemesterData {
+    pub semester: usize,
+    #[serde(default)]
+    pub timetable: Vec<Lesson>,
+    /// Start of the exam, such as "2022-11-23T01:00:00.000Z".
+    #[serde(default, alias = "examDate")]
+    pub exam_date: Option<String>,
+    /// Length of the exam in minutes.
+    #[serde(default, alias = "examDuration")]
+    pub exam_duration: Option<u32>,
+    #[serde(default, alias = "covidZones")]
+    pub covid_zones: Vec<String>,
+}
+
+/// Flags that NUSMods gives a module, all of which may be missing.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Attributes {
+    /// Year-long module.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub year: Option<bool>,
+    /// Has S/U option for undergraduates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub su: Option<bool>,
+    /// Has S/U option for graduates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grsu: Option<bool>,
+    /// SkillsFuture Series.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssgf: Option<bool>,
+    /// SkillsFuture Funded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sfs: Option<bool>,
+    /// Lab-based module.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lab: Option<bool>,
+    /// Independent study module.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ism: Option<bool>,
+    /// Undergraduate Research Opportunities Programme.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub urop: Option<bool>,
+    /// Final year project.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fyp: Option<bool>,
+    /// Offered in the first Module Planning Exercise.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mpes1: Option<bool>,
+    /// Offered in the second Module Planning Exercise.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mpes2: Option<bool>,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Module {
     #[serde(default, alias = "acadYear")]
@@ -35,6 +135,8 @@ pub struct Module {
     pub faculty: String,
     #[serde(default)]
     pub prerequisite: String,
+    #[serde(default)]
+    pub corequisite: String,
     #[serde(default, alias = "moduleCredit")]
     pub module_credit: String,
     #[serde(default, alias = "moduleCode")]
@@ -45,6 +147,22 @@ pub struct Module {
     pub fulfill_requirements: Vec<String>,
     #[serde(default)]
     pub workload: Workload,
+    #[serde(default, alias = "semesterData")]
+    pub semester_data: Vec<SemesterData>,
+    #[serde(default)]
+    pub attributes: Attributes,
+    /// Such as "Letter Grade".
+    #[serde(default, alias = "gradingBasisDescription")]
+    pub grading_basis_description: Option<String>,
+    /// Prerequisites as written by the registrar, which `prereqtree` is
+    /// built from. Only given from AY2022/2023 onwards.
+    #[serde(default, alias = "prereqRule")]
+    pub prereq_rule: Option<String>,
+    /// Codes that the module is also listed under.
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    #[serde(default, alias = "additionalInformation")]
+    pub additional_information: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -72,3 +190,67 @@ impl Default for Workload {
         Self::Numbers(vec![])
     }
 }
+
+#[test]
+fn schema_test() {
+    let ay2021 = r#"{
+        "acadYear": "2021/2022",
+        "moduleCode": "CS2040S",
+        "moduleCredit": "4",
+        "prerequisite": "CS1101S",
+        "prereqTree": "CS1101S",
+        "attributes": { "su": true, "mpes1": true },
+        "semesterData": [{
+            "semester": 1,
+            "timetable": [{
+                "classNo": "1",
+                "startTime": "1000",
+                "endTime": "1200",
+                "weeks": [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13],
+                "venue": "I3-AUD",
+                "day": "Monday",
+                "lessonType": "Lecture",
+                "size": 400,
+                "covidZone": "B"
+            }],
+            "covidZones": ["B"],
+            "examDate": "2021-11-23T01:00:00.000Z",
+            "examDuration": 120
+        }]
+    }"#;
+    let ay2022 = r#"{
+        "acadYear": "2022/2023",
+        "moduleCode": "CS2040S",
+        "moduleCredit": "4",
+        "prereqTree": { "or": ["CS1010S", "CS1101S"] },
+        "prereqRule": "PROGRAM_TYPES IF_IN Undergraduate Degree THEN COURSES (1) CS1010S:D, CS1101S:D",
+        "gradingBasisDescription": "Letter Grade",
+        "aliases": ["CS2040"],
+        "attributes": {},
+        "semesterData": [{
+            "semester": 2,
+            "timetable": [{
+                "classNo": "01",
+                "startTime": "1400",
+                "endTime": "1600",
+                "weeks": { "start": "2023-01-16", "end": "2023-04-14", "weekInterval": 2 },
+                "day": "Friday",
+                "lessonType": "Tutorial"
+            }]
+        }]
+    }"#;
+    let old: Module = serde_json::from_str(ay2021).unwrap();
+    assert_eq!(old.attributes.su, Some(true));
+    assert_eq!(old.prereq_rule, None);
+    assert_eq!(old.semester_data[0].timetable[0].size, Some(400));
+    let new: Module = serde_json::from_str(ay2022).unwrap();
+    assert_eq!(new.attributes, Attributes::default());
+    assert_eq!(new.aliases, ["CS2040"]);
+    let weeks = &new.semester_data[0].timetable[0].weeks;
+    assert!(matches!(weeks, Weeks::Range { week_interval: Some(2), .. }));
+    // a lesson missing fields doesn't reject the whole module
+    let lesson = r#"{ "classNo": "1", "lessonType": "Lecture" }"#;
+    let lesson: Lesson = serde_json::from_str(lesson).unwrap();
+    assert_eq!(lesson.day, "");
+    assert!(matches!(lesson.weeks, Weeks::Numbers(v) if v.is_empty()));
+}
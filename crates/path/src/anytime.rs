@@ -0,0 +1,132 @@
+use crate::planner::Node;
+use crate::{Path, Planner, Strategy};
+use std::time::{Duration, Instant};
+
+/// Limits on how much work an anytime search may do. A search with no
+/// limits runs until it proves that its best path is optimal.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Budget {
+    /// Wall-clock time allowed.
+    pub time: Option<Duration>,
+    /// Number of paths that may be expanded.
+    pub nodes: Option<usize>,
+}
+
+impl Budget {
+    /// Checks if a search that began at `start` and has expanded `expanded`
+    /// paths has used up the budget.
+    pub(crate) fn spent(&self, start: Instant, expanded: usize) -> bool {
+        self.time.is_some_and(|t| start.elapsed() >= t)
+            || self.nodes.is_some_and(|n| expanded >= n)
+    }
+}
+
+/// Best path found by an anytime search.
+pub struct Anytime {
+    pub best: Option<Path>,
+    /// Whether `best` is known to be optimal. This is false if the budget
+    /// ran out before the search could prove it.
+    pub optimal: bool,
+    /// Number of paths taken off the beam and expanded.
+    pub expanded: usize,
+}
+
+impl Planner<'_> {
+    /// Beam search that reports each better path as soon as it is found, and
+    /// stops cleanly when the budget runs out.
+    ///
+    /// Each round keeps only the `width` most promising paths at every step,
+    /// ranked by the same lower bound that `Strategy::AStar` uses. If a round
+    /// had to drop paths, the next round doubles the width. A round that
+    /// never drops a path has searched everything, so its best is optimal.
+    pub fn anytime<F>(
+        &self,
+        width: usize,
+        budget: Budget,
+        mut on_improve: F,
+    ) -> Anytime
+    where
+        F: FnMut(&Path),
+    {
+        let start = Instant::now();
+        let mut result = Anytime { best: None, optimal: false, expanded: 0 };
+        let mut best: Option<(f32, usize)> = None;
+        let mut width = width.max(1);
+
+        loop {
+            let mut beam = vec![self.bounded(self.start())];
+            let mut truncated = false;
+            while !beam.is_empty() {
+                let mut next = vec![];
+                for Node { path, .. } in beam {
+                    if budget.spent(start, result.expanded) {
+                        return result;
+                    }
+                    if path.len() >= self.query.max_sems {
+                        continue;
+                    }
+                    result.expanded += 1;
+                    for node in self.expand(path, None) {
+                        let finished = node.finished;
+                        let node = Node { finished, ..self.bounded(node.path) };
+                        let key = (node.cost, node.path.len());
+                        if best.is_some_and(|best| key >= best) {
+                            continue;
+                        }
+                        if node.finished {
+                            best = Some(key);
+                            on_improve(&node.path);
+                            result.best = Some(node.path);
+                        } else {
+                            next.push(node);
+                        }
+                    }
+                }
+                // most promising first
+                next.sort_by(|a, b| b.cmp(a));
+                truncated |= next.len() > width;
+                next.truncate(width);
+                beam = next;
+            }
+            if !truncated {
+                result.optimal = true;
+                return result;
+            }
+            width *= 2;
+        }
+    }
+
+    /// Keys a path by the A* lower bound on its final cost.
+    fn bounded(&self, path: Path) -> Node {
+        let cost = self.estimate(&path, Strategy::AStar);
+        Node { cost, finished: false, path }
+    }
+}
+
+#[test]
+fn anytime_test() {
+    use crate::test_utils::module;
+    use crate::Query;
+    let sample_space = vec![
+        module("A", &[1, 2, 3, 4], "", &[]),
+        module("B", &[1, 2, 3, 4], "", &[]),
+        module("C", &[1, 2, 3, 4], "", &[]),
+        module("D", &[1, 2, 3, 4], r#"{ "or": ["A", "B"] }"#, &[]),
+        module("E", &[1, 2, 3, 4], r#"{ "and": ["C", "D"] }"#, &[]),
+    ];
+    let query = Query::new(sample_space, vec!["E".to_string()]);
+    let planner = Planner::new(&query).unwrap();
+
+    let mut found = vec![];
+    let result = planner.anytime(1, Budget::default(), |p| found.push(p.len()));
+    assert!(result.optimal);
+    assert_eq!(result.best.unwrap().len(), 3);
+    // every reported path is better than the one before it
+    assert!(found.windows(2).all(|w| w[0] > w[1]));
+
+    // a spent budget stops the search before it can prove anything
+    let budget = Budget { nodes: Some(0), ..Budget::default() };
+    let result = planner.anytime(1, budget, |_| ());
+    assert!(!result.optimal);
+    assert!(result.best.is_none());
+}
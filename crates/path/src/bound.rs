@@ -0,0 +1,177 @@
+use crate::{Path, Planner};
+use prereqtree::PrereqTree;
+use std::collections::{HashMap, HashSet};
+
+/// Lower bounds on the final cost of a path, used to guide A* search. They
+/// never overestimate, so the first finished path popped is still optimal.
+impl Planner<'_> {
+    /// Targets that are neither done nor in progress.
+    pub(crate) fn targets_left<'b>(
+        &'b self,
+        path: &'b Path,
+    ) -> Vec<&'b String> {
+        let taken =
+            |t: &&String| path.done().contains(*t) || path.doing().contains(t);
+        self.required.iter().filter(|t| !taken(t)).collect()
+    }
+
+    /// Fewest semesters that a path can take once it completes all targets.
+    ///
+    /// Each target needs at least as many semesters as its longest chain of
+    /// unmet prerequisites, and at least as many as it takes to fit the
+    /// modules that must still be taken. Those are counted without
+    /// duplicates, so that a module shared by two branches of a tree, or by
+    /// two targets, is only counted once.
+    pub(crate) fn semesters_bound(&self, path: &Path) -> usize {
+        let (len, max_mods) = (path.len(), self.query.max_mods.max(1));
+        if self.is_finished(path) {
+            return len + (path.doing_count() > 0) as usize;
+        }
+        let mut memo = HashMap::new();
+        let depth = self
+            .required
+            .iter()
+            .map(|t| self.depth(t, path, &mut memo))
+            .max()
+            .unwrap_or(0);
+
+        let mut taken: HashSet<String> = path.done().clone();
+        taken.extend(path.doing().iter().cloned());
+        let left = self.targets_left(path);
+        let trees = left.iter().filter_map(|t| self.modules.get(t.as_str()));
+        let trees: Vec<_> = trees.map(|m| m.prereqtree()).collect();
+        // modules that every way of completing the targets needs
+        let mut needed: HashSet<&str> =
+            left.iter().map(|t| t.as_str()).collect();
+        trees.iter().for_each(|t| needed.extend(necessary(t, &taken)));
+        let each = trees.iter().map(|t| 1 + fewest(t, &taken)).max();
+        let count = needed.len().max(each.unwrap_or(0));
+        let slots = max_mods.saturating_sub(path.doing_count());
+        let extra = count.saturating_sub(slots);
+        let by_count = 1 + extra.div_ceil(max_mods);
+
+        len + depth.max(by_count)
+    }
+
+    /// Lowest peak workload that a path can have once it completes all
+    /// targets: every target left must still fit into some semester.
+    pub(crate) fn peak_bound(&self, path: &Path) -> f32 {
+        let peak = self.hours(path).into_iter().fold(0.0, f32::max);
+        self.targets_left(path)
+            .into_iter()
+            .map(|t| *self.hours.get(t).unwrap_or(&0.0))
+            .fold(peak, f32::max)
+    }
+
+    /// Number of semesters, starting from the current one, needed to
+    /// complete a module.
+    fn depth(
+        &self,
+        code: &str,
+        path: &Path,
+        memo: &mut HashMap<String, usize>,
+    ) -> usize {
+        if path.done().contains(code) {
+            return 0;
+        }
+        if path.doing().iter().any(|v| v == code) {
+            return 1;
+        }
+        if let Some(depth) = memo.get(code) {
+            return *depth;
+        }
+        // guards against cyclic prerequisites, which can never be met
+        memo.insert(code.to_string(), 0);
+        let depth = match self.modules.get(code) {
+            Some(m) => 1 + self.tree_depth(m.prereqtree(), path, memo),
+            None => 1,
+        };
+        memo.insert(code.to_string(), depth);
+        depth
+    }
+
+    fn tree_depth(
+        &self,
+        tree: &PrereqTree,
+        path: &Path,
+        memo: &mut HashMap<String, usize>,
+    ) -> usize {
+        use PrereqTree::*;
+        match tree {
+            Only(code) if code.is_empty() => 0,
+            Only(code) => self.depth(code, path, memo),
+            Cond(_) => 0,
+            And { and } => {
+                let depths = and.iter().map(|t| self.tree_depth(t, path, memo));
+                depths.max().unwrap_or(0)
+            }
+            Or { or } => {
+                let depths = or.iter().map(|t| self.tree_depth(t, path, memo));
+                depths.min().unwrap_or(0)
+            }
+        }
+    }
+}
+
+/// Modules in every set of modules that satisfies a tree, other than those
+/// already taken.
+fn necessary<'t>(
+    tree: &'t PrereqTree,
+    taken: &HashSet<String>,
+) -> HashSet<&'t str> {
+    use PrereqTree::*;
+    match tree {
+        Only(code) if code.is_empty() || taken.contains(code) => HashSet::new(),
+        Only(code) => HashSet::from([code.as_str()]),
+        Cond(_) => HashSet::new(),
+        And { and } => and.iter().flat_map(|t| necessary(t, taken)).collect(),
+        Or { or } => {
+            let mut sets = or.iter().map(|t| necessary(t, taken));
+            let first = sets.next().unwrap_or_default();
+            sets.fold(first, |a, b| a.intersection(&b).copied().collect())
+        }
+    }
+}
+
+/// Fewest modules that can satisfy a tree, other than those already taken.
+/// Branches of an `And` may share modules, so only the largest counts.
+fn fewest(tree: &PrereqTree, taken: &HashSet<String>) -> usize {
+    use PrereqTree::*;
+    match tree {
+        Only(code) if code.is_empty() || taken.contains(code) => 0,
+        Only(_) => 1,
+        Cond(_) => 0,
+        And { and } => and.iter().map(|t| fewest(t, taken)).max().unwrap_or(0),
+        Or { or } => or.iter().map(|t| fewest(t, taken)).min().unwrap_or(0),
+    }
+}
+
+#[test]
+fn semesters_bound_test() {
+    use crate::test_utils::module;
+    use crate::{Query, Strategy};
+    // A alone unlocks T, although it is counted in both branches of T's tree
+    let tree = r#"{ "and": [{ "or": ["A", "B"] }, { "or": ["A", "C"] }] }"#;
+    let sample_space = vec![
+        module("A", &[1, 2, 3, 4], "", &[]),
+        module("B", &[1, 2, 3, 4], "", &[]),
+        module("C", &[1, 2, 3, 4], "", &[]),
+        module("T", &[1, 2, 3, 4], tree, &[]),
+    ];
+    let mut query = Query::new(sample_space, vec!["T".to_string()]);
+    query.max_mods = 1;
+    query.strategy = Strategy::AStar;
+    let planner = Planner::new(&query).unwrap();
+    assert_eq!(planner.semesters_bound(&planner.start()), 2);
+    assert_eq!(planner.search().routes[0].len(), 2);
+
+    // B and D are needed by both targets, but only have to be taken once
+    let tree = r#"{ "and": ["B", "D"] }"#;
+    query.sample_space.push(module("D", &[1, 2, 3, 4], "", &[]));
+    query.sample_space.push(module("U", &[1, 2, 3, 4], tree, &[]));
+    query.sample_space.push(module("V", &[1, 2, 3, 4], tree, &[]));
+    query.targets = vec!["U".to_string(), "V".to_string()];
+    let planner = Planner::new(&query).unwrap();
+    assert_eq!(planner.semesters_bound(&planner.start()), 4);
+    assert_eq!(planner.search().routes[0].len(), 4);
+}
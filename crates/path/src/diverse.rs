@@ -0,0 +1,126 @@
+use crate::planner::Node;
+use crate::{Budget, Path, Planner};
+use std::collections::{BinaryHeap, HashSet};
+use std::time::Instant;
+
+/// How far apart two paths are, used to tell real alternatives from paths
+/// that only shuffle the same plan around.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Diversity {
+    /// Number of modules taken by only one of the two paths. Paths that take
+    /// the same modules in a different order are the same.
+    Modules,
+    /// Number of modules that are not taken in the same semester by both
+    /// paths. Moving one module to another semester counts once.
+    Schedule,
+}
+
+impl Diversity {
+    pub fn distance(&self, a: &Path, b: &Path) -> usize {
+        match self {
+            Self::Modules => {
+                let (a, b) = (taken(a), taken(b));
+                a.symmetric_difference(&b).count()
+            }
+            Self::Schedule => {
+                let (a, b) = (scheduled(a), scheduled(b));
+                let moved = a.symmetric_difference(&b).map(|(code, _)| code);
+                moved.collect::<HashSet<_>>().len()
+            }
+        }
+    }
+}
+
+/// Every module taken by a path.
+fn taken(path: &Path) -> HashSet<&String> {
+    path.record().iter().flatten().chain(path.doing()).collect()
+}
+
+/// Every module taken by a path, with the semester it is taken in.
+fn scheduled(path: &Path) -> HashSet<(&String, usize)> {
+    let record = path.record().iter().chain([path.doing()]);
+    let sems = record.enumerate();
+    sems.flat_map(|(i, sem)| sem.iter().map(move |code| (code, i))).collect()
+}
+
+impl Planner<'_> {
+    /// Finds up to `k` paths in order of cost, where each path is at least
+    /// `min_distance` away from every path before it under `diversity`.
+    ///
+    /// Unlike `search`, this does not stop at the optimal cost, and never
+    /// prunes dominated states, since those may lead to the alternatives
+    /// being looked for. So that a large `min_distance` or a small plan
+    /// space can't make it try every ordering of every module, the search
+    /// stops when `budget` runs out, with the paths found so far.
+    pub fn k_best(
+        &self,
+        k: usize,
+        diversity: Diversity,
+        min_distance: usize,
+        budget: Budget,
+    ) -> Vec<Path> {
+        let (start, mut expanded) = (Instant::now(), 0);
+        let mut routes: Vec<Path> = vec![];
+        let mut pq = BinaryHeap::new();
+        pq.push(self.node(self.start(), false));
+
+        while let Some(Node { finished, path, .. }) = pq.pop() {
+            if routes.len() >= k || budget.spent(start, expanded) {
+                break;
+            }
+            if finished {
+                let far =
+                    |r: &Path| diversity.distance(r, &path) >= min_distance;
+                if routes.iter().all(far) {
+                    routes.push(path);
+                }
+                continue;
+            }
+            if path.len() >= self.query.max_sems {
+                continue;
+            }
+            expanded += 1;
+            pq.extend(self.expand(path, None));
+        }
+        routes
+    }
+}
+
+#[test]
+fn k_best_test() {
+    use crate::test_utils::module;
+    use crate::Query;
+    let sample_space = vec![
+        module("A", &[1, 2, 3, 4], "", &[]),
+        module("B", &[1, 2, 3, 4], "", &[]),
+        module("D", &[1, 2, 3, 4], r#"{ "or": ["A", "B"] }"#, &[]),
+    ];
+    let mut query = Query::new(sample_space, vec!["D".to_string()]);
+    query.max_mods = 1;
+    let planner = Planner::new(&query).unwrap();
+    let k_best = |k, diversity, min_distance| {
+        planner.k_best(k, diversity, min_distance, Budget::default())
+    };
+    let codes = |p: &Path| p.record().concat();
+
+    // A or B can be taken first, and taking both costs one more semester
+    let routes = k_best(5, Diversity::Modules, 1);
+    assert_eq!(
+        routes.iter().map(codes).collect::<Vec<_>>(),
+        [vec!["A", "D"], vec!["B", "D"], vec!["A", "B", "D"],]
+    );
+
+    // taking both is only one module away from either
+    let routes = k_best(5, Diversity::Modules, 2);
+    assert_eq!(routes.len(), 2);
+
+    // but taking them in the other order is a different schedule
+    let routes = k_best(5, Diversity::Schedule, 1);
+    assert_eq!(routes.len(), 4);
+    assert_eq!(codes(&routes[3]), ["B", "A", "D"]);
+
+    // no two paths are this far apart, so only the budget ends the search
+    let budget = Budget { nodes: Some(50), ..Budget::default() };
+    let routes = planner.k_best(5, Diversity::Modules, 10, budget);
+    assert_eq!(routes.len(), 1);
+}
@@ -0,0 +1,104 @@
+use crate::{Path, Planner};
+use std::collections::HashSet;
+
+/// What happens to a plan when one of its modules is failed.
+#[derive(Debug)]
+pub struct Impact {
+    /// Modules later in the plan whose prerequisites are no longer met,
+    /// directly or through another broken module, with their semester.
+    pub broken: Vec<(String, usize)>,
+    /// Earliest semester in which each target can still be completed, given
+    /// everything up to the failed semester. `None` if it no longer can.
+    pub earliest: Vec<(String, Option<usize>)>,
+}
+
+impl Planner<'_> {
+    /// Finds what breaks in a plan, in the same shape as `Path::record`, if
+    /// `code` is failed in semester `sem`. Semesters are indices into the
+    /// plan.
+    pub fn impact(
+        &self,
+        plan: &[Vec<String>],
+        code: &str,
+        sem: usize,
+    ) -> Impact {
+        let mut lost: HashSet<&str> = HashSet::from([code]);
+        let mut done: HashSet<String> = HashSet::new();
+        let mut broken = vec![];
+        for (i, codes) in plan.iter().enumerate() {
+            if i > sem {
+                let profile = self.profile(i, &done);
+                for code in codes {
+                    let tree =
+                        self.modules.get(code.as_str()).map(|m| m.prereqtree());
+                    if tree.is_some_and(|t| !t.satisfied_with(&done, &profile))
+                    {
+                        broken.push((code.to_string(), i));
+                        lost.insert(code);
+                    }
+                }
+            }
+            let kept = codes.iter().filter(|c| !lost.contains(c.as_str()));
+            done.extend(kept.cloned());
+        }
+
+        // the semesters up to the failure have already happened
+        let mut start = Path::new();
+        for codes in plan.iter().take(sem + 1) {
+            codes.iter().filter(|c| *c != code).for_each(|c| start.mark(c));
+            start.next_sem();
+        }
+        let earliest = self
+            .required
+            .iter()
+            .map(|target| (target.to_string(), self.earliest(&start, target)))
+            .collect();
+        Impact { broken, earliest }
+    }
+
+    /// Earliest semester in which `target` can be completed by a path that
+    /// continues from `start`.
+    fn earliest(&self, start: &Path, target: &String) -> Option<usize> {
+        let taken = start.record().iter().position(|s| s.contains(target));
+        taken.or_else(|| {
+            let planner =
+                Planner { required: vec![target.to_string()], ..self.clone() };
+            let routes = planner.search_from(start.clone()).routes;
+            routes.first().map(|p| p.len() - 1)
+        })
+    }
+}
+
+#[test]
+fn impact_test() {
+    use crate::test_utils::module;
+    use crate::Query;
+    let sample_space = vec![
+        module("A", &[1, 2, 3, 4], "", &[]),
+        module("B", &[1, 2, 3, 4], "", &[]),
+        module("C", &[1, 2, 3, 4], r#""A""#, &[]),
+        module("D", &[1, 2, 3, 4], r#"{ "or": ["B", "C"] }"#, &[]),
+        module("E", &[1, 2, 3, 4], r#""C""#, &[]),
+        module("F", &[1], "", &[]),
+    ];
+    let targets = vec!["D".to_string(), "E".to_string(), "F".to_string()];
+    let query = Query::new(sample_space, targets);
+    let planner = Planner::new(&query).unwrap();
+    let plan: Vec<Vec<String>> = [&["A", "F"][..], &["C"], &[], &[], &["E"]]
+        .iter()
+        .map(|sem| sem.iter().map(|c| c.to_string()).collect())
+        .collect();
+
+    // C needs A, and E needs C, so both break
+    let impact = planner.impact(&plan, "A", 0);
+    let broken = vec![("C".to_string(), 1), ("E".to_string(), 4)];
+    assert_eq!(impact.broken, broken);
+    // D can still be taken through B, but E needs A and C to be retaken
+    let earliest: Vec<_> =
+        impact.earliest.into_iter().map(|(_, e)| e).collect();
+    assert_eq!(earliest, vec![Some(2), Some(3), Some(0)]);
+
+    // failing E breaks nothing
+    let impact = planner.impact(&plan, "E", 4);
+    assert!(impact.broken.is_empty());
+}
@@ -1,6 +1,36 @@
+mod anytime;
+mod bound;
+mod diverse;
+mod impact;
+mod memo;
+mod planner;
+mod pool;
+mod pref;
+mod rank;
+mod repair;
+mod report;
+mod solver;
+#[cfg(test)]
+mod test_utils;
+mod timetable;
+mod validate;
+
 use std::collections::HashSet;
 use std::mem;
-use types::Module;
+use types::{Module, Semester};
+
+pub use anytime::{Anytime, Budget};
+pub use diverse::Diversity;
+pub use impact::Impact;
+pub use planner::{Objective, Planner, Query, Search, Strategy};
+pub use pool::Pool;
+pub use pref::Preferences;
+pub use rank::{Criterion, Ranking};
+pub use repair::{diff, Change, Disruption, Repair};
+pub use report::Report;
+pub use solver::Solver;
+pub use timetable::{timetable, Slot};
+pub use validate::{Issue, Violation};
 
 /// In a graph representation of all possible module plans, each node represents
 /// a path. This is that node
@@ -34,11 +64,26 @@ impl Path {
         self.global_sem += 1;
     }
 
+    /// Skips a semester in which no modules are taken, such as one spent on
+    /// an internship or exchange, and credits `external` modules as done in
+    /// it. The offering cycle still advances.
+    pub fn skip_sem(&mut self, external: &[String]) {
+        let mut external = external.to_vec();
+        external.sort();
+        self.doing = external;
+        self.next_sem();
+    }
+
     /// Mark a module as done during this semester.
     pub fn mark(&mut self, module_code: &str) {
         self.doing.push(module_code.to_string());
     }
 
+    /// Gets a reference to the modules taken in each completed semester.
+    pub fn record(&self) -> &Vec<Vec<String>> {
+        &self.record
+    }
+
     pub fn mod_count(&self) -> usize {
         self.record.iter().fold(0, |a, v| a + v.len()) + self.doing_count()
     }
@@ -54,9 +99,19 @@ impl Path {
         self.doing.contains(code)
     }
 
-    /// Gets the actual semester: a value in [1, 4]
-    fn sem(&self) -> usize {
-        self.global_sem % 4 + 1
+    /// Gets the actual semester of the academic year
+    fn sem(&self) -> Semester {
+        Semester::of_index(self.global_sem)
+    }
+
+    /// Gets a reference to the modules done before the current semester.
+    pub fn done(&self) -> &HashSet<String> {
+        &self.done
+    }
+
+    /// Gets a reference to the modules taken in the current semester.
+    pub fn doing(&self) -> &Vec<String> {
+        &self.doing
     }
 
     /// Get number of modules currently doing.
@@ -73,6 +128,7 @@ impl Path {
     /// 1. Must be offered in this sem.
     /// 2. Must have prerequisites fulfilled by modules `done`.
     /// 3. Must be lexicographically after the last module in `doing`.
+    /// 4. Must not have been done before.
     pub fn choices<'a>(
         &self,
         sample_space: &'a Vec<Module>,
@@ -86,30 +142,37 @@ impl Path {
             .filter(|m| m.prereqtree().satisfied_by(&self.done))
             // 3. Must be lexicographically after the last module in `doing`.
             .filter(|m| last.map_or(true, |v| m.code().cmp(v).is_gt()))
+            // 4. Must not have been done before.
+            .filter(|m| !self.done.contains(m.code()))
             .map(|m| m.code())
             .collect()
     }
 }
 
 // for BinaryHeap implementation (min heap)
+//
+// Shorter paths are popped first. Among paths of equal length, the one with
+// more modules is popped first, and the remaining ties are broken by the
+// modules taken, so that the order of the heap is always deterministic.
 use std::cmp::{Ord, Ordering, PartialOrd};
 impl PartialEq for Path {
     fn eq(&self, rhs: &Path) -> bool {
-        self.len() == rhs.len()
+        self.cmp(rhs).is_eq()
     }
 }
 impl Eq for Path {}
 impl PartialOrd for Path {
     fn partial_cmp(&self, rhs: &Path) -> Option<Ordering> {
-        match rhs.len().cmp(&self.len()) {
-            Ordering::Equal => None,
-            v => Some(v),
-        }
+        Some(self.cmp(rhs))
     }
 }
 impl Ord for Path {
     fn cmp(&self, rhs: &Path) -> Ordering {
-        rhs.len().cmp(&self.len())
+        rhs.len()
+            .cmp(&self.len())
+            .then_with(|| self.mod_count().cmp(&rhs.mod_count()))
+            .then_with(|| rhs.record.cmp(&self.record))
+            .then_with(|| rhs.doing.cmp(&self.doing))
     }
 }
 
@@ -133,3 +196,25 @@ fn test() {
     println!("{:?}", "a".cmp("A"));
     // assert!(false)
 }
+
+#[test]
+fn heap_order_test() {
+    use std::collections::BinaryHeap;
+    let path = |sems: usize, codes: &[&str]| {
+        let mut p = Path::new();
+        codes.iter().for_each(|code| p.mark(code));
+        (0..sems).for_each(|_| p.next_sem());
+        p
+    };
+    let mut pq = BinaryHeap::new();
+    pq.push(path(2, &["A"]));
+    pq.push(path(1, &["B"]));
+    pq.push(path(1, &["A"]));
+    pq.push(path(1, &["A", "B"]));
+    let popped: Vec<_> = std::iter::from_fn(|| pq.pop()).collect();
+    assert_eq!(popped[0], path(1, &["A", "B"]));
+    assert_eq!(popped[1], path(1, &["A"]));
+    assert_eq!(popped[2], path(1, &["B"]));
+    assert_eq!(popped[3], path(2, &["A"]));
+    assert_ne!(path(1, &["A"]), path(1, &["B"]));
+}
@@ -0,0 +1,63 @@
+use std::collections::{HashMap, HashSet};
+
+/// Table of the states reached at the end of each semester.
+///
+/// A state is the set of modules done by then, and the cost of reaching it.
+/// One state dominates another of the same semester if it has done at least
+/// the same modules at no greater cost, since every path that continues from
+/// the smaller set can be followed from the larger one.
+#[derive(Default)]
+pub(crate) struct Visited(HashMap<usize, Vec<(HashSet<String>, f32)>>);
+
+impl Visited {
+    /// Records a state. Returns false if an equal or dominating state has
+    /// already been recorded, in which case this one need not be explored.
+    pub fn insert(
+        &mut self,
+        sem: usize,
+        done: &HashSet<String>,
+        cost: f32,
+    ) -> bool {
+        let seen = self.0.entry(sem).or_default();
+        if seen.iter().any(|(s, c)| *c <= cost && done.is_subset(s)) {
+            return false;
+        }
+        seen.retain(|(s, c)| !(cost <= *c && s.is_subset(done)));
+        seen.push((done.clone(), cost));
+        true
+    }
+
+    /// Checks if a recorded state has since been dominated by a later one.
+    pub fn dominated(
+        &self,
+        sem: usize,
+        done: &HashSet<String>,
+        cost: f32,
+    ) -> bool {
+        self.0.get(&sem).is_some_and(|seen| {
+            seen.iter().any(|(s, c)| {
+                *c <= cost && s.len() > done.len() && done.is_subset(s)
+            })
+        })
+    }
+}
+
+#[test]
+fn visited_test() {
+    let set = |v: &[&str]| -> HashSet<String> {
+        v.iter().map(|v| v.to_string()).collect()
+    };
+    let mut visited = Visited::default();
+    assert!(visited.insert(1, &set(&["A"]), 1.0));
+    // equal states are only explored once
+    assert!(!visited.insert(1, &set(&["A"]), 1.0));
+    // states of other semesters are independent
+    assert!(visited.insert(2, &set(&["A"]), 1.0));
+    // a superset dominates the states it contains
+    assert!(visited.insert(1, &set(&["A", "B"]), 1.0));
+    assert!(visited.dominated(1, &set(&["A"]), 1.0));
+    assert!(!visited.insert(1, &set(&["B"]), 1.0));
+    // but not if it was reached at a greater cost
+    assert!(visited.insert(1, &set(&["B"]), 0.5));
+    assert!(!visited.dominated(1, &set(&["B"]), 0.5));
+}
@@ -0,0 +1,669 @@
+use crate::memo::Visited;
+use crate::report::sem_hours;
+use crate::timetable::timetable;
+use crate::{Path, Pool, Preferences};
+use prereqtree::Profile;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use types::{Credits, Module, Result};
+
+/// What the planner minimises.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Objective {
+    /// Fewest semesters taken.
+    Semesters,
+    /// Lowest weekly workload in the heaviest semester.
+    PeakWorkload,
+    /// Lowest total cost under the query's `Preferences`.
+    Preferences,
+}
+
+/// How the planner orders the paths that it explores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// Explores paths in order of their cost so far.
+    Uniform,
+    /// Explores paths in order of a lower bound on their final cost, which
+    /// skips paths that can't beat the best one found.
+    AStar,
+}
+
+/// Everything the planner needs to know to search for paths.
+pub struct Query {
+    /// Every module that may be taken.
+    pub sample_space: Vec<Module>,
+    /// Modules that must be completed.
+    pub targets: Vec<String>,
+    /// Sets of modules that each need enough of their members completed.
+    pub pools: Vec<Pool>,
+    /// Maximum number of modules taken in one semester.
+    pub max_mods: usize,
+    /// Maximum number of semesters that a path may take.
+    pub max_sems: usize,
+    /// Maximum weekly workload hours in one semester.
+    pub max_hours: Option<f32>,
+    /// Maximum modular credits taken in one semester. Modules worth a range
+    /// of credits count at their most.
+    pub max_credits: Option<f32>,
+    /// Modules that must be taken in a given semester, as an index into
+    /// `Path::record`.
+    pub pinned: Vec<(String, usize)>,
+    /// Modules that must be done by the end of a given semester, as an index
+    /// into `Path::record`.
+    pub deadlines: Vec<(String, usize)>,
+    /// Semesters in which no modules can be taken, such as for an exchange,
+    /// internship or leave of absence, as an index into `Path::record`. Each
+    /// comes with the modules credited externally in that semester, if any.
+    pub blocked: Vec<(usize, Vec<String>)>,
+    /// Modules that must not be taken, and how much the others are wanted.
+    /// Exclusions apply under every objective, and the rest only apply with
+    /// `Objective::Preferences`.
+    pub preferences: Preferences,
+    /// The student at the start of the path, which year-standing, credit
+    /// and programme prerequisites are checked against.
+    pub profile: Profile,
+    /// Allow two modules in one semester to have exams that overlap. Either
+    /// way, `Planner::validate` reports such clashes.
+    pub allow_exam_clashes: bool,
+    /// Only take modules together if each can get a class of every lesson
+    /// type without clashes. Only the planner checks this, as the solver
+    /// can't model the choice of classes.
+    pub check_timetable: bool,
+    pub objective: Objective,
+    pub strategy: Strategy,
+    /// Skip states that are equal to or dominated by one already seen.
+    /// Pruning keeps at least one optimal path but drops the rest, so turn
+    /// it off to rank every optimal path.
+    pub prune: bool,
+}
+
+impl Query {
+    pub fn new(sample_space: Vec<Module>, targets: Vec<String>) -> Self {
+        Self {
+            sample_space,
+            targets,
+            pools: vec![],
+            max_mods: 5,
+            max_sems: 16,
+            max_hours: None,
+            max_credits: None,
+            pinned: vec![],
+            deadlines: vec![],
+            blocked: vec![],
+            preferences: Preferences::default(),
+            profile: Profile::default(),
+            allow_exam_clashes: false,
+            check_timetable: false,
+            objective: Objective::Semesters,
+            strategy: Strategy::Uniform,
+            prune: true,
+        }
+    }
+
+    /// The student at the start of a semester, as an index into
+    /// `Path::record`, after completing another `credits` MCs.
+    pub(crate) fn profile_at(&self, sem: usize, credits: f32) -> Profile {
+        let mut profile = self.profile.clone();
+        profile.year = profile.year.saturating_add((sem / 4) as u8);
+        profile.credits += credits;
+        profile
+    }
+
+    /// Every module that a path must complete: the targets, and the modules
+    /// that are pinned or have deadlines.
+    pub fn required(&self) -> Vec<String> {
+        let mut required = self.targets.clone();
+        let scheduled = self.pinned.iter().chain(&self.deadlines);
+        for (code, _) in scheduled {
+            if !required.contains(code) {
+                required.push(code.to_string());
+            }
+        }
+        required
+    }
+}
+
+/// A path in the search queue, keyed by the cost of the objective.
+pub(crate) struct Node {
+    pub cost: f32,
+    /// Whether the path has completed all targets.
+    pub finished: bool,
+    pub path: Path,
+}
+
+// for BinaryHeap implementation (min heap)
+impl PartialEq for Node {
+    fn eq(&self, rhs: &Node) -> bool {
+        self.cmp(rhs).is_eq()
+    }
+}
+impl Eq for Node {}
+impl PartialOrd for Node {
+    fn partial_cmp(&self, rhs: &Node) -> Option<Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+impl Ord for Node {
+    fn cmp(&self, rhs: &Node) -> Ordering {
+        rhs.cost.total_cmp(&self.cost).then_with(|| self.path.cmp(&rhs.path))
+    }
+}
+
+/// Paths found by a search, and how much work it took to find them.
+pub struct Search {
+    pub routes: Vec<Path>,
+    /// Number of paths taken off the queue and expanded.
+    pub expanded: usize,
+    /// Number of paths put onto the queue.
+    pub generated: usize,
+}
+
+/// Searches for paths that complete a query's targets.
+#[derive(Clone)]
+pub struct Planner<'a> {
+    pub(crate) query: &'a Query,
+    pub(crate) required: Vec<String>,
+    pub(crate) modules: HashMap<&'a str, &'a Module>,
+    pub(crate) hours: HashMap<String, f32>,
+    pub(crate) credits: HashMap<String, Credits>,
+}
+
+impl<'a> Planner<'a> {
+    /// Creates a planner for a query, or an error if the credits of a
+    /// module in its sample space can't be read.
+    pub fn new(query: &'a Query) -> Result<Self> {
+        let sample_space = &query.sample_space;
+        Ok(Self {
+            query,
+            required: query.required(),
+            modules: sample_space
+                .iter()
+                .map(|m| (m.code().as_str(), m))
+                .collect(),
+            hours: sample_space
+                .iter()
+                .map(|m| (m.to_code(), m.workload_hours()))
+                .collect(),
+            credits: sample_space
+                .iter()
+                .map(|m| Ok((m.to_code(), m.credits()?)))
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    /// Weekly workload hours of each semester of a path, including the
+    /// semester in progress.
+    pub fn hours(&self, path: &Path) -> Vec<f32> {
+        let mut hours: Vec<f32> =
+            path.record().iter().map(|s| sem_hours(s, &self.hours)).collect();
+        hours.push(sem_hours(path.doing(), &self.hours));
+        hours
+    }
+
+    fn cost(&self, path: &Path) -> f32 {
+        self.estimate(path, self.query.strategy)
+    }
+
+    /// Cost of a path so far, or with `Strategy::AStar`, a lower bound on
+    /// its final cost.
+    pub(crate) fn estimate(&self, path: &Path, strategy: Strategy) -> f32 {
+        use {Objective::*, Strategy::*};
+        match (self.query.objective, strategy) {
+            (Semesters, Uniform) => path.len() as f32,
+            (Semesters, AStar) => self.semesters_bound(path) as f32,
+            (PeakWorkload, Uniform) => {
+                self.hours(path).into_iter().fold(0.0, f32::max)
+            }
+            (PeakWorkload, AStar) => self.peak_bound(path),
+            (Preferences, Uniform) => self.preference_cost(path),
+            (Preferences, AStar) => self.preference_bound(path),
+        }
+    }
+
+    pub(crate) fn node(&self, path: Path, finished: bool) -> Node {
+        Node { cost: self.cost(&path), finished, path }
+    }
+
+    /// Modules that can be added to the semester in progress without
+    /// exceeding the workload cap.
+    fn choices(&self, path: &Path) -> Vec<&'a String> {
+        let mut choices = path.choices(&self.query.sample_space);
+        choices.retain(|c| self.query.preferences.allows(c));
+        // prerequisites that are not modules
+        let profile = self.profile(path.len(), path.done());
+        choices.retain(|c| {
+            let tree = self.modules[c.as_str()].prereqtree();
+            !tree.has_conditions() || tree.satisfied_with(path.done(), &profile)
+        });
+        if !self.query.allow_exam_clashes {
+            let doing = path.doing().iter().map(|c| self.modules[c.as_str()]);
+            let doing: Vec<_> = doing.collect();
+            choices.retain(|c| {
+                let module = self.modules[c.as_str()];
+                !doing.iter().any(|m| m.exam_clashes_with(module, path.sem()))
+            });
+        }
+        if self.query.check_timetable {
+            let mut doing: Vec<_> =
+                path.doing().iter().map(|c| self.modules[c.as_str()]).collect();
+            choices.retain(|c| {
+                doing.push(self.modules[c.as_str()]);
+                let fits = timetable(&doing, path.sem()).is_some();
+                doing.pop();
+                fits
+            });
+        }
+        // pinned modules can only be taken in their own semester
+        let pinned = &self.query.pinned;
+        choices.retain(|c| {
+            pinned.iter().all(|(p, sem)| p != *c || *sem == path.len())
+        });
+        if let Some(max) = self.query.max_hours {
+            let doing = sem_hours(path.doing(), &self.hours);
+            choices.retain(|c| doing + self.hours[*c] <= max);
+        }
+        if let Some(max) = self.query.max_credits {
+            let most = |c: &str| self.credits[c].max;
+            let doing: f32 = path.doing().iter().map(|c| most(c)).sum();
+            choices.retain(|c| doing + most(c) <= max);
+        }
+        choices
+    }
+
+    /// The student at the start of a semester, as an index into
+    /// `Path::record`, having completed the modules in `done`.
+    pub(crate) fn profile(
+        &self,
+        sem: usize,
+        done: &HashSet<String>,
+    ) -> Profile {
+        let credits = done
+            .iter()
+            .filter_map(|c| self.credits.get(c))
+            .map(|c| c.min)
+            .sum();
+        self.query.profile_at(sem, credits)
+    }
+
+    /// Empty path, moved past any blocked semesters at the start.
+    pub(crate) fn start(&self) -> Path {
+        let mut path = Path::new();
+        self.skip_blocked(&mut path);
+        path
+    }
+
+    /// Skips every blocked semester that a path has reached, if it has not
+    /// started the semester in progress.
+    fn skip_blocked(&self, path: &mut Path) {
+        let blocked = &self.query.blocked;
+        while path.doing_count() == 0 {
+            match blocked.iter().find(|(sem, _)| *sem == path.len()) {
+                Some((_, external)) => path.skip_sem(external),
+                None => break,
+            }
+        }
+    }
+
+    /// Checks that every pinned module and every deadline in the semesters
+    /// that a path has completed has been met.
+    fn on_schedule(&self, path: &Path) -> bool {
+        let query = self.query;
+        let scheduled = query.pinned.iter().chain(&query.deadlines);
+        scheduled
+            .filter(|(_, sem)| *sem < path.len())
+            .all(|(code, _)| path.done().contains(code))
+    }
+
+    /// Ends the semester in progress, along with any blocked semesters right
+    /// after it. Returns `None` if the resulting state misses a pinned module
+    /// or a deadline, or has already been reached some other way.
+    fn close(
+        &self,
+        mut path: Path,
+        visited: Option<&mut Visited>,
+    ) -> Option<Node> {
+        path.next_sem();
+        self.skip_blocked(&mut path);
+        if !self.on_schedule(&path) {
+            return None;
+        }
+        // modules credited externally may complete the path
+        let finished = self.is_finished(&path);
+        let node = self.node(path, finished);
+        let (sem, done) = (node.path.len(), node.path.done());
+        match visited.is_none_or(|v| v.insert(sem, done, node.cost)) {
+            true => Some(node),
+            false => None,
+        }
+    }
+
+    /// Every path one step on from `path`: either a module is added to the
+    /// semester in progress, or the semester ends.
+    pub(crate) fn expand(
+        &self,
+        path: Path,
+        mut visited: Option<&mut Visited>,
+    ) -> Vec<Node> {
+        let (query, mut nodes) = (self.query, vec![]);
+        let choices = self.choices(&path);
+        if path.doing_count() < query.max_mods && !choices.is_empty() {
+            for next_mod in choices {
+                let mut path = path.clone();
+                path.mark(next_mod);
+                let finished = self.is_finished(&path);
+                if finished {
+                    path.next_sem();
+                    if !self.on_schedule(&path) {
+                        continue;
+                    }
+                }
+                nodes.push(self.node(path, finished));
+            }
+            // a lighter semester may lower the peak workload, or leave room
+            // for a module in a semester that it is preferred in
+            if query.objective != Objective::Semesters {
+                nodes.extend(self.close(path, visited.as_deref_mut()));
+            }
+        } else {
+            nodes.extend(self.close(path, visited));
+        }
+        nodes
+    }
+
+    /// Finds paths that are optimal under the query's objective. Paths with
+    /// equal cost are all returned, in no particular order, unless they are
+    /// pruned.
+    pub fn search(&self) -> Search {
+        self.search_from(self.start())
+    }
+
+    /// Same as `search`, but only looks at paths that continue from `start`.
+    pub(crate) fn search_from(&self, mut start: Path) -> Search {
+        self.skip_blocked(&mut start);
+        let (query, mut routes) = (self.query, vec![]);
+        let (mut expanded, mut generated) = (0, 1);
+        let mut best: Option<(f32, usize)> = None;
+        let mut visited = Visited::default();
+        let mut pq = BinaryHeap::new();
+        pq.push(self.node(start, false));
+
+        while let Some(Node { cost, finished, path }) = pq.pop() {
+            let key = (cost, path.len());
+            match best {
+                Some(best) if key > best => break,
+                Some(best) if key == best && !finished => continue,
+                _ => (),
+            }
+            if finished {
+                best = Some(key);
+                routes.push(path);
+                continue;
+            }
+            if query.prune
+                && path.doing_count() == 0
+                && visited.dominated(path.len(), path.done(), cost)
+            {
+                continue;
+            }
+            if path.len() >= query.max_sems {
+                continue;
+            }
+            expanded += 1;
+            let visited = query.prune.then_some(&mut visited);
+            let nodes = self.expand(path, visited);
+            generated += nodes.len();
+            pq.extend(nodes);
+        }
+        Search { routes, expanded, generated }
+    }
+}
+
+#[test]
+fn search_test() {
+    use crate::test_utils::module;
+    let sample_space = vec![
+        module("A", &[1, 2, 3, 4], "", &[4.0, 4.0]),
+        module("B", &[1, 2, 3, 4], "", &[4.0, 4.0]),
+        module("C", &[1, 2, 3, 4], r#"{ "and": ["A", "B"] }"#, &[2.0]),
+    ];
+    let mut query = Query::new(sample_space, vec!["C".to_string()]);
+    query.prune = false;
+    let routes = Planner::new(&query).unwrap().search().routes;
+    assert_eq!(routes.len(), 1);
+    assert_eq!(routes[0].record(), &vec![vec!["A", "B"], vec!["C"]]);
+
+    // a workload cap forces A and B into separate semesters
+    query.max_hours = Some(10.0);
+    let routes = Planner::new(&query).unwrap().search().routes;
+    assert_eq!(routes.len(), 2);
+    assert!(routes.iter().all(|r| r.len() == 3));
+
+    // spreading the load lowers the peak even without a cap
+    query.max_hours = None;
+    query.objective = Objective::PeakWorkload;
+    let planner = Planner::new(&query).unwrap();
+    let routes = planner.search().routes;
+    assert!(!routes.is_empty());
+    for route in routes {
+        let peak = planner.hours(&route).into_iter().fold(0.0, f32::max);
+        assert_eq!(peak, 8.0);
+    }
+}
+
+#[test]
+fn prune_test() {
+    use crate::test_utils::module;
+    // independent modules can be taken in any order, so many paths reach
+    // the same set of modules done.
+    let mut sample_space: Vec<_> = ["A", "B", "C", "D", "E", "F", "G"]
+        .iter()
+        .map(|code| module(code, &[1, 2, 3, 4], "", &[]))
+        .collect();
+    let tree = r#"{ "and": ["A", "B", "C", "D", "E", "F", "G"] }"#;
+    sample_space.push(module("Z", &[1, 2, 3, 4], tree, &[]));
+    let mut query = Query::new(sample_space, vec!["Z".to_string()]);
+    query.max_mods = 2;
+    let all = Planner::new(&query).unwrap().search().routes;
+    query.prune = false;
+    let unpruned = Planner::new(&query).unwrap().search().routes;
+    assert_eq!(all[0].len(), 5);
+    assert!(unpruned.iter().all(|r| r.len() == 5));
+    assert!(all.len() < unpruned.len());
+}
+
+#[test]
+fn astar_test() {
+    use crate::test_utils::module;
+    let sample_space = vec![
+        module("A", &[1, 2, 3, 4], "", &[4.0]),
+        module("B", &[1, 2, 3, 4], "", &[6.0]),
+        module("C", &[1, 2, 3, 4], "", &[2.0]),
+        module("D", &[1, 2, 3, 4], r#"{ "or": ["A", "B"] }"#, &[2.0]),
+        module("E", &[1, 2, 3, 4], r#"{ "and": ["C", "D"] }"#, &[4.0]),
+        module("F", &[1, 2, 3, 4], r#""D""#, &[2.0]),
+    ];
+    let targets = vec!["E".to_string(), "F".to_string()];
+    let mut query = Query::new(sample_space, targets);
+    use Objective::*;
+    for objective in [Semesters, PeakWorkload, Preferences] {
+        query.objective = objective;
+        query.strategy = Strategy::Uniform;
+        let planner = Planner::new(&query).unwrap();
+        let uniform = planner.search();
+        query.strategy = Strategy::AStar;
+        let planner = Planner::new(&query).unwrap();
+        let astar = planner.search();
+        let cost = |p: &Path| match objective {
+            Objective::Semesters => p.len() as f32,
+            Objective::PeakWorkload => {
+                planner.hours(p).into_iter().fold(0.0, f32::max)
+            }
+            Objective::Preferences => planner.preference_cost(p),
+        };
+        assert!(!astar.routes.is_empty());
+        assert_eq!(cost(&astar.routes[0]), cost(&uniform.routes[0]));
+        assert!(astar.expanded <= uniform.expanded);
+    }
+}
+
+#[test]
+fn blocked_test() {
+    use crate::test_utils::module;
+    use crate::Solver;
+    let sample_space = vec![
+        module("A", &[1, 2], "", &[]),
+        module("B", &[1, 2], r#""A""#, &[]),
+        module("C", &[1, 2], r#""X""#, &[]),
+    ];
+    let mut query = Query::new(sample_space, vec!["B".to_string()]);
+    // an internship in Y1S2 pushes B to the next regular semester
+    query.blocked = vec![(1, vec![])];
+    let routes = Planner::new(&query).unwrap().search().routes;
+    assert_eq!(
+        routes[0].record(),
+        &vec![vec!["A"], vec![], vec![], vec![], vec!["B"]]
+    );
+    assert_eq!(
+        Solver::new(&query).unwrap().solve().unwrap().routes[0].len(),
+        5
+    );
+
+    // an exchange module counts towards prerequisites afterwards
+    query.targets = vec!["C".to_string()];
+    query.blocked = vec![(0, vec!["X".to_string()])];
+    let routes = Planner::new(&query).unwrap().search().routes;
+    assert!(routes.iter().all(|r| r.len() == 2 && r.record()[0] == ["X"]));
+    let solved = Solver::new(&query).unwrap().solve().unwrap().routes;
+    assert_eq!(solved[0].record(), &vec![vec!["X"], vec!["C"]]);
+}
+
+#[test]
+fn condition_test() {
+    use crate::test_utils::module;
+    use crate::Solver;
+    use prereqtree::{Condition, PrereqTree};
+    let with_tree = |mut m: Module, tree: PrereqTree| {
+        m.set_tree(tree);
+        m
+    };
+    let sample_space = vec![
+        module("A", &[1, 2], "", &[]),
+        with_tree(
+            module("B", &[1, 2], "", &[]),
+            PrereqTree::And {
+                and: vec![
+                    PrereqTree::Only("A".to_string()),
+                    PrereqTree::Cond(Condition::Year(2)),
+                ],
+            },
+        ),
+        with_tree(
+            module("C", &[1, 2], "", &[]),
+            PrereqTree::Cond(Condition::Credits(8.0)),
+        ),
+        module("D", &[1, 2], "", &[]),
+    ];
+    let mut query = Query::new(sample_space, vec!["B".to_string()]);
+    // B waits for the second year
+    let routes = Planner::new(&query).unwrap().search().routes;
+    assert_eq!(routes[0].len(), 5);
+    assert_eq!(routes[0].record()[4], ["B"]);
+    assert_eq!(
+        Solver::new(&query).unwrap().solve().unwrap().routes[0].len(),
+        5
+    );
+
+    // unless the student is already in it
+    query.profile.year = 2;
+    assert_eq!(Planner::new(&query).unwrap().search().routes[0].len(), 2);
+    assert_eq!(
+        Solver::new(&query).unwrap().solve().unwrap().routes[0].len(),
+        2
+    );
+
+    // C needs two modules done first
+    query.targets = vec!["C".to_string()];
+    let routes = Planner::new(&query).unwrap().search().routes;
+    assert_eq!(routes[0].len(), 2);
+    let solved = Solver::new(&query).unwrap().solve().unwrap().routes;
+    assert_eq!(solved[0].len(), 2);
+    assert_eq!(
+        Planner::new(&query).unwrap().validate(solved[0].record()),
+        vec![]
+    );
+    query.profile.credits = 8.0;
+    assert_eq!(
+        Solver::new(&query).unwrap().solve().unwrap().routes[0].len(),
+        1
+    );
+    query.profile.credits = 4.0;
+    let issues =
+        Planner::new(&query).unwrap().validate(&[vec!["C".to_string()]]);
+    let violation = crate::Violation::UnmetConditions("C".to_string());
+    assert_eq!(issues[0].violation, violation);
+
+    // the programme restriction is guessed from the prerequisite text, and
+    // only holds once the student's programmes are known
+    let json = r#"{
+        "moduleCode": "E",
+        "moduleCredit": "4",
+        "prerequisite": "Only for Computer Science students."
+    }"#;
+    let mut restricted =
+        Module::from(serde_json::from_str::<nusmods::Module>(json).unwrap());
+    restricted.set_semesters(crate::test_utils::semesters(&[1, 2]));
+    let mut query = Query::new(vec![restricted], vec!["E".to_string()]);
+    assert_eq!(Planner::new(&query).unwrap().search().routes[0].len(), 1);
+    assert_eq!(
+        Solver::new(&query).unwrap().solve().unwrap().routes[0].len(),
+        1
+    );
+    query.profile.programmes = vec!["Business Analytics".to_string()];
+    assert!(Planner::new(&query).unwrap().search().routes.is_empty());
+    assert!(Solver::new(&query).unwrap().solve().unwrap().routes.is_empty());
+    query.profile.programmes.push("Computer Science".to_string());
+    assert_eq!(Planner::new(&query).unwrap().search().routes[0].len(), 1);
+}
+
+#[test]
+fn exam_clash_test() {
+    use crate::test_utils::module;
+    use crate::{Solver, Violation};
+    use types::{Exam, Semester};
+    let with_exam = |mut m: Module, date: &str| {
+        let date = date.to_string();
+        m.set_exams(vec![Exam {
+            semester: Semester::new(1).unwrap(),
+            date,
+            duration: 120,
+        }]);
+        m
+    };
+    let sample_space = vec![
+        with_exam(module("A", &[1, 2], "", &[]), "2022-11-23T01:00:00Z"),
+        with_exam(module("B", &[1, 2], "", &[]), "2022-11-23T02:00:00Z"),
+        with_exam(module("C", &[1, 2], "", &[]), "2022-11-23T03:00:00Z"),
+    ];
+    let targets = ["A", "B", "C"].map(|c| c.to_string()).to_vec();
+    let plan = [targets.clone()];
+    let mut query = Query::new(sample_space, targets);
+    // B clashes with both A and C in the first semester
+    let routes = Planner::new(&query).unwrap().search().routes;
+    assert_eq!(routes[0].record(), &vec![vec!["A", "C"], vec!["B"]]);
+    assert_eq!(
+        Solver::new(&query).unwrap().solve().unwrap().routes[0].len(),
+        2
+    );
+    let issues = Planner::new(&query).unwrap().validate(&plan);
+    let clash =
+        |a: &str, b: &str| Violation::ExamClash(a.to_string(), b.to_string());
+    let violations: Vec<_> = issues.into_iter().map(|i| i.violation).collect();
+    assert_eq!(violations, [clash("A", "B"), clash("B", "C")]);
+
+    query.allow_exam_clashes = true;
+    assert_eq!(Planner::new(&query).unwrap().search().routes[0].len(), 1);
+    assert_eq!(
+        Solver::new(&query).unwrap().solve().unwrap().routes[0].len(),
+        1
+    );
+}
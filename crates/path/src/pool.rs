@@ -0,0 +1,80 @@
+use crate::{Path, Planner};
+
+/// A target that is met by taking enough modules out of a set, such as "2 of
+/// these 8 electives". Every module in the pool has to be in the query's
+/// sample space to be picked.
+#[derive(Debug, Clone, Default)]
+pub struct Pool {
+    pub modules: Vec<String>,
+    /// Fewest modules to take from the pool.
+    pub count: usize,
+    /// Fewest modular credits to take from the pool, if any.
+    pub credits: Option<f32>,
+}
+
+impl Pool {
+    pub fn new(modules: Vec<String>, count: usize) -> Self {
+        Self { modules, count, credits: None }
+    }
+
+    /// Checks if a path has taken enough modules from the pool, counting the
+    /// semester in progress. `credits` gives the credits of each module.
+    pub fn satisfied_by<F>(&self, path: &Path, credits: F) -> bool
+    where
+        F: Fn(&str) -> f32,
+    {
+        let taken: Vec<&String> = self
+            .modules
+            .iter()
+            .filter(|m| path.done().contains(*m) || path.doing().contains(m))
+            .collect();
+        let total: f32 = taken.iter().map(|m| credits(m)).sum();
+        taken.len() >= self.count && self.credits.is_none_or(|c| total >= c)
+    }
+}
+
+impl Planner<'_> {
+    /// Checks if a path has completed every required module and every pool.
+    pub(crate) fn is_finished(&self, path: &Path) -> bool {
+        let credits =
+            |code: &str| self.credits.get(code).map_or(0.0, |c| c.min);
+        path.is_done(&self.required)
+            && self.query.pools.iter().all(|p| p.satisfied_by(path, credits))
+    }
+}
+
+#[test]
+fn pool_test() {
+    use crate::test_utils::module;
+    use crate::{Query, Solver};
+    let sample_space = vec![
+        module("A", &[1, 2, 3, 4], "", &[]),
+        module("B", &[1, 2, 3, 4], r#""A""#, &[]),
+        module("C", &[2], "", &[]),
+        module("D", &[1, 2, 3, 4], "", &[]),
+        module("E", &[1, 2, 3, 4], "", &[]),
+    ];
+    let mut query = Query::new(sample_space, vec![]);
+    query.max_mods = 2;
+    let codes = ["B", "C", "D", "E"].map(|c| c.to_string()).to_vec();
+
+    // the two modules that can be taken right away are picked
+    query.pools = vec![Pool::new(codes.clone(), 2)];
+    let routes = Planner::new(&query).unwrap().search().routes;
+    assert_eq!(routes[0].record(), &vec![vec!["D", "E"]]);
+    assert_eq!(
+        Solver::new(&query).unwrap().solve().unwrap().routes[0].len(),
+        1
+    );
+
+    // each module is worth 4 credits, so this needs all four of them
+    let pool = Pool { credits: Some(16.0), ..Pool::new(codes, 0) };
+    query.pools = vec![pool];
+    let routes = Planner::new(&query).unwrap().search().routes;
+    assert_eq!(routes[0].len(), 3);
+    assert!(routes[0].record().concat().contains(&"A".to_string()));
+    assert_eq!(
+        Solver::new(&query).unwrap().solve().unwrap().routes[0].len(),
+        3
+    );
+}
@@ -0,0 +1,139 @@
+use crate::{Path, Planner};
+use prereqtree::PrereqTree;
+use std::collections::{HashMap, HashSet};
+use types::Semester;
+
+/// What a student wants out of a plan, on top of completing its targets.
+#[derive(Debug, Clone, Default)]
+pub struct Preferences {
+    /// Modules that must never be scheduled.
+    pub excluded: HashSet<String>,
+    /// How strongly each module is preferred. Positive weights make a module
+    /// more likely to be picked and negative weights less likely. Modules
+    /// without a weight have a weight of 0.
+    pub weights: HashMap<String, f32>,
+    /// Semesters in which a module would rather be taken.
+    pub semesters: HashMap<String, Vec<Semester>>,
+}
+
+impl Preferences {
+    /// Checks if a module may be scheduled at all.
+    pub fn allows(&self, code: &str) -> bool {
+        !self.excluded.contains(code)
+    }
+
+    /// Cost of taking a module, which halves with each unit of weight. This
+    /// is 1 for a module without a weight, so that a cheapest set of modules
+    /// is also a smallest one when no weights are given. Returns `None` if
+    /// the module is excluded.
+    pub fn cost(&self, code: &str) -> Option<f32> {
+        let weight = self.weights.get(code).copied().unwrap_or(0.0);
+        self.allows(code).then(|| (-weight).exp2())
+    }
+
+    /// Cost of taking a module in semester `sem`. Taking it outside of its
+    /// preferred semesters costs 1 more.
+    pub fn cost_in(&self, code: &str, sem: Semester) -> Option<f32> {
+        let preferred = self.semesters.get(code);
+        let penalty = match preferred.is_some_and(|s| !s.contains(&sem)) {
+            true => 1.0,
+            false => 0.0,
+        };
+        self.cost(code).map(|cost| cost + penalty)
+    }
+
+    /// Cheapest modules to take that unlock a prerequisite tree, without any
+    /// excluded module. Returns `None` if the tree needs an excluded module.
+    pub fn min_path(&self, tree: &PrereqTree) -> Option<Vec<String>> {
+        tree.min_path_by(&|code| self.cost(code))
+    }
+}
+
+impl Planner<'_> {
+    /// Total preference cost of the modules in a path so far.
+    pub(crate) fn preference_cost(&self, path: &Path) -> f32 {
+        let prefs = &self.query.preferences;
+        let sems = path.record().iter().chain([path.doing()]).enumerate();
+        let costs = sems.flat_map(|(i, codes)| {
+            codes
+                .iter()
+                .filter_map(move |c| prefs.cost_in(c, Semester::of_index(i)))
+        });
+        costs.sum()
+    }
+
+    /// Lowest preference cost that a path can have once it completes all
+    /// targets: every target left must still be taken at some cost.
+    pub(crate) fn preference_bound(&self, path: &Path) -> f32 {
+        let prefs = &self.query.preferences;
+        let left = self.targets_left(path).into_iter();
+        let cost: f32 = left.filter_map(|t| prefs.cost(t)).sum();
+        self.preference_cost(path) + cost
+    }
+}
+
+#[test]
+fn min_path_test() {
+    let tree = |json: &str| serde_json::from_str::<PrereqTree>(json).unwrap();
+    let mut prefs = Preferences {
+        excluded: HashSet::from(["D".to_string()]),
+        weights: HashMap::from([
+            ("B".to_string(), 2.0),
+            ("C".to_string(), 2.0),
+        ]),
+        ..Default::default()
+    };
+
+    // B and C together cost less than A alone
+    let mut path = prefs
+        .min_path(&tree(r#"{ "or": ["A", { "and": ["B", "C"] }] }"#))
+        .unwrap();
+    path.sort();
+    assert_eq!(path, ["B", "C"]);
+    // D is never picked, even over a module that is not wanted
+    prefs.weights.insert("A".to_string(), -4.0);
+    assert_eq!(
+        prefs.min_path(&tree(r#"{ "or": ["A", "D"] }"#)).unwrap(),
+        ["A"]
+    );
+    assert_eq!(prefs.min_path(&tree(r#"{ "and": ["A", "D"] }"#)), None);
+}
+
+#[test]
+fn preferences_test() {
+    use crate::test_utils::{module, semesters};
+    use crate::{Objective, Query, Solver};
+    let sample_space = vec![
+        module("A", &[1, 2, 3, 4], "", &[]),
+        module("B", &[1, 2, 3, 4], "", &[]),
+        module("C", &[1, 2, 3, 4], "", &[]),
+        module("D", &[1, 2, 3, 4], r#"{ "or": ["A", "B", "C"] }"#, &[]),
+    ];
+    let mut query = Query::new(sample_space, vec!["D".to_string()]);
+    query.max_mods = 1;
+    query.objective = Objective::Preferences;
+    query.preferences.excluded = HashSet::from(["A".to_string()]);
+    query.preferences.weights = HashMap::from([("C".to_string(), 1.0)]);
+    let routes = Planner::new(&query).unwrap().search().routes;
+    assert_eq!(routes[0].record(), &vec![vec!["C"], vec!["D"]]);
+    assert_eq!(
+        Solver::new(&query).unwrap().solve().unwrap().routes[0].record(),
+        routes[0].record()
+    );
+
+    // C is only wanted in the second semester of the year
+    let semesters = HashMap::from([("C".to_string(), semesters(&[2]))]);
+    query.preferences.semesters = semesters;
+    let routes = Planner::new(&query).unwrap().search().routes;
+    assert_eq!(routes[0].record(), &vec![vec![], vec!["C"], vec!["D"]]);
+    assert_eq!(
+        Solver::new(&query).unwrap().solve().unwrap().routes[0].record(),
+        routes[0].record()
+    );
+
+    // exclusions hold under every objective
+    query.objective = Objective::Semesters;
+    query.preferences.excluded.extend(["B".to_string(), "C".to_string()]);
+    assert!(Planner::new(&query).unwrap().search().routes.is_empty());
+    assert!(Solver::new(&query).unwrap().solve().unwrap().routes.is_empty());
+}
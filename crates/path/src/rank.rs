@@ -0,0 +1,191 @@
+use crate::Path;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::str::FromStr;
+use types::{Error, Module};
+
+/// One measure by which two candidate paths are compared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Criterion {
+    /// Fewer semesters taken is better.
+    Semesters,
+    /// Fewer modules taken is better.
+    Modules,
+    /// Fewer modular credits taken is better.
+    Credits,
+    /// A more even spread of weekly workload hours across semesters is
+    /// better.
+    Variance,
+    /// More target modules completed before the final semester is better.
+    EarlyTargets,
+}
+
+impl FromStr for Criterion {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "semesters" => Ok(Self::Semesters),
+            "modules" => Ok(Self::Modules),
+            "credits" => Ok(Self::Credits),
+            "variance" => Ok(Self::Variance),
+            "early" => Ok(Self::EarlyTargets),
+            v => Err(Error::InvalidArgument(format!("unknown criterion: {v}"))),
+        }
+    }
+}
+
+/// Ranks candidate paths by a list of criteria, in order of priority. Ties
+/// on every criterion are broken by comparing the semester records
+/// lexicographically, so the final order is always deterministic.
+pub struct Ranking {
+    criteria: Vec<Criterion>,
+    targets: Vec<String>,
+    credits: HashMap<String, f32>,
+    hours: HashMap<String, f32>,
+}
+
+impl Ranking {
+    /// Creates a ranking over the modules in `sample_space`, where `targets`
+    /// are the modules that the query wants completed. Fails if the credits
+    /// of a module can't be read.
+    pub fn new(
+        criteria: Vec<Criterion>,
+        sample_space: &[Module],
+        targets: &[String],
+    ) -> types::Result<Self> {
+        Ok(Self {
+            criteria,
+            targets: targets.to_vec(),
+            credits: sample_space
+                .iter()
+                .map(|m| Ok((m.to_code(), m.credits()?.min)))
+                .collect::<types::Result<_>>()?,
+            hours: sample_space
+                .iter()
+                .map(|m| (m.to_code(), m.workload_hours()))
+                .collect(),
+        })
+    }
+
+    /// Reads a comma-separated list of criteria, such as
+    /// "semesters,credits,early".
+    pub fn parse_criteria(list: &str) -> types::Result<Vec<Criterion>> {
+        list.split(',').map(Criterion::from_str).collect()
+    }
+
+    /// The default order of criteria.
+    pub fn default_criteria() -> Vec<Criterion> {
+        use Criterion::*;
+        vec![Semesters, Modules, Credits, Variance, EarlyTargets]
+    }
+
+    /// Gets the criteria, in order of priority.
+    pub fn criteria(&self) -> &Vec<Criterion> {
+        &self.criteria
+    }
+
+    /// Compares two paths. The better path is ordered first.
+    pub fn compare(&self, a: &Path, b: &Path) -> Ordering {
+        self.criteria
+            .iter()
+            .map(|c| self.compare_by(c, a, b))
+            .find(|v| v.is_ne())
+            .unwrap_or_else(|| a.record().cmp(b.record()))
+    }
+
+    /// Sorts paths from best to worst.
+    pub fn sort(&self, paths: &mut [Path]) {
+        paths.sort_by(|a, b| self.compare(a, b))
+    }
+
+    fn compare_by(&self, c: &Criterion, a: &Path, b: &Path) -> Ordering {
+        match c {
+            Criterion::Semesters => a.len().cmp(&b.len()),
+            Criterion::Modules => a.mod_count().cmp(&b.mod_count()),
+            Criterion::Credits => self.credits(a).total_cmp(&self.credits(b)),
+            Criterion::Variance => {
+                self.variance(a).total_cmp(&self.variance(b))
+            }
+            Criterion::EarlyTargets => self.early(b).cmp(&self.early(a)),
+        }
+    }
+
+    /// Sum of a value of each module, in each semester of the path.
+    fn per_sem(path: &Path, values: &HashMap<String, f32>) -> Vec<f32> {
+        let value = |code: &String| *values.get(code).unwrap_or(&0.0);
+        path.record().iter().map(|sem| sem.iter().map(value).sum()).collect()
+    }
+
+    fn credits(&self, path: &Path) -> f32 {
+        Self::per_sem(path, &self.credits).iter().sum()
+    }
+
+    /// Variance of the weekly workload hours across semesters.
+    fn variance(&self, path: &Path) -> f32 {
+        let sems = Self::per_sem(path, &self.hours);
+        if sems.is_empty() {
+            return 0.0;
+        }
+        let mean = sems.iter().sum::<f32>() / sems.len() as f32;
+        sems.iter().map(|v| (v - mean).powi(2)).sum::<f32>() / sems.len() as f32
+    }
+
+    /// Number of target modules done before the final semester.
+    fn early(&self, path: &Path) -> usize {
+        let record = path.record();
+        let before_last = &record[..record.len().saturating_sub(1)];
+        self.targets
+            .iter()
+            .filter(|t| before_last.iter().any(|sem| sem.contains(t)))
+            .count()
+    }
+}
+
+#[test]
+fn ranking_test() {
+    use Criterion::*;
+    let targets = vec!["B".to_string(), "C".to_string()];
+    let path = |record: &[&[&str]]| {
+        let mut p = Path::new();
+        for sem in record {
+            sem.iter().for_each(|code| p.mark(code));
+            p.next_sem();
+        }
+        p
+    };
+    let short = path(&[&["A", "B"], &["C"]]);
+    let long = path(&[&["A"], &["B"], &["C"]]);
+    let late = path(&[&["A"], &["B", "C"]]);
+
+    let ranking = Ranking::new(vec![Semesters], &[], &targets).unwrap();
+    assert!(ranking.compare(&short, &long).is_lt());
+    // ties on every criterion fall back to the record
+    assert!(ranking.compare(&short, &late).is_gt());
+
+    let ranking =
+        Ranking::new(vec![EarlyTargets, Semesters], &[], &targets).unwrap();
+    let mut paths = vec![late.clone(), long.clone(), short.clone()];
+    ranking.sort(&mut paths);
+    assert_eq!(paths[0].record(), short.record());
+    assert_eq!(paths[1].record(), long.record());
+    assert_eq!(paths[2].record(), late.record());
+
+    // the even path has 4 hours in each semester, the other 2 and 6
+    use crate::test_utils::module;
+    let sample_space = vec![
+        module("A", &[1], "", &[2.0]),
+        module("B", &[1], "", &[2.0]),
+        module("C", &[1], "", &[4.0]),
+    ];
+    let ranking =
+        Ranking::new(vec![Variance], &sample_space, &targets).unwrap();
+    let even = path(&[&["A", "B"], &["C"]]);
+    let uneven = path(&[&["A"], &["B", "C"]]);
+    assert!(ranking.compare(&even, &uneven).is_lt());
+
+    assert_eq!(
+        Ranking::parse_criteria("early, semesters").unwrap(),
+        vec![EarlyTargets, Semesters]
+    );
+    assert!(Ranking::parse_criteria("semesters,foo").is_err());
+}
@@ -0,0 +1,160 @@
+use crate::solver::Goal;
+use crate::{Path, Solver};
+use std::collections::HashMap;
+use types::Result;
+
+/// Something that breaks an existing plan.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Disruption {
+    /// The module can no longer be taken in the given semester, as an index
+    /// into the plan, because it was cancelled or failed there.
+    Remove(String, usize),
+    /// The module is no longer offered at all.
+    NotOffered(String),
+}
+
+impl Disruption {
+    /// First semester of a plan that the disruption affects. A module that
+    /// is no longer offered affects the plan from where it is first taken.
+    fn sem(&self, plan: &[Vec<String>]) -> usize {
+        match self {
+            Self::Remove(_, sem) => *sem,
+            Self::NotOffered(code) => {
+                plan.iter().position(|s| s.contains(code)).unwrap_or(plan.len())
+            }
+        }
+    }
+}
+
+/// One module whose semester differs between two plans. A semester of
+/// `None` means that the module is not taken in that plan.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub code: String,
+    pub from: Option<usize>,
+    pub to: Option<usize>,
+}
+
+/// A repaired plan, and how it differs from the original.
+pub struct Repair {
+    pub path: Path,
+    pub changes: Vec<Change>,
+}
+
+impl Solver<'_> {
+    /// Repairs an existing plan, in the same shape as `Path::record`, so
+    /// that it survives a disruption and still completes the query.
+    ///
+    /// Semesters before the disrupted one are already over, so they are kept
+    /// as they are. The rest of the repaired plan keeps as many of the
+    /// original semester assignments as possible, and is as short as
+    /// possible after that. Modules in the
+    /// plan that are not in the query's sample space can't be kept. Returns
+    /// `None` if no plan can complete the query after the disruption, and an
+    /// error if the solver fails.
+    pub fn repair(
+        &self,
+        plan: &[Vec<String>],
+        disruption: &Disruption,
+    ) -> Result<Option<Repair>> {
+        let index = &self.index;
+        let plan_index = plan.iter().enumerate().flat_map(|(sem, codes)| {
+            codes
+                .iter()
+                .filter_map(move |c| Some((*index.get(c.as_str())?, sem)))
+        });
+        let banned = match disruption {
+            Disruption::Remove(code, sem) => match index.get(code.as_str()) {
+                Some(m) => vec![(*m, *sem)],
+                None => vec![],
+            },
+            Disruption::NotOffered(code) => match index.get(code.as_str()) {
+                Some(m) => (0..self.query.max_sems).map(|s| (*m, s)).collect(),
+                None => vec![],
+            },
+        };
+        let solver = Solver {
+            query: self.query,
+            index: self.index.clone(),
+            credits: self.credits.clone(),
+            plan: plan_index.collect(),
+            banned: banned.into_iter().collect(),
+            frozen: disruption.sem(plan),
+        };
+        let Some(path) = solver.lexicographic(Goal::Changes, Goal::Length)?
+        else {
+            return Ok(None);
+        };
+        let changes = diff(plan, path.record());
+        Ok(Some(Repair { path, changes }))
+    }
+}
+
+/// Every module that is taken in a different semester, or only taken in one
+/// of the two plans, in order of module code.
+pub fn diff(from: &[Vec<String>], to: &[Vec<String>]) -> Vec<Change> {
+    let semesters = |plan: &[Vec<String>]| -> HashMap<String, usize> {
+        let sems = plan.iter().enumerate();
+        sems.flat_map(|(i, s)| s.iter().map(move |c| (c.to_string(), i)))
+            .collect()
+    };
+    let (from, to) = (semesters(from), semesters(to));
+    let mut codes: Vec<&String> = from.keys().chain(to.keys()).collect();
+    codes.sort();
+    codes.dedup();
+    codes
+        .into_iter()
+        .map(|code| Change {
+            code: code.to_string(),
+            from: from.get(code).copied(),
+            to: to.get(code).copied(),
+        })
+        .filter(|c| c.from != c.to)
+        .collect()
+}
+
+#[test]
+fn repair_test() {
+    use crate::test_utils::module;
+    use crate::Query;
+    let sample_space = vec![
+        module("A", &[1, 2], "", &[]),
+        module("B", &[1, 2], "", &[]),
+        module("C", &[1, 2], "", &[]),
+        module("D", &[1, 2], r#"{ "and": ["A", "B"] }"#, &[]),
+    ];
+    let targets = vec!["C".to_string(), "D".to_string()];
+    let mut query = Query::new(sample_space, targets);
+    query.max_mods = 2;
+    let solver = Solver::new(&query).unwrap();
+    let plan: Vec<Vec<String>> = vec![
+        vec!["A".to_string(), "B".to_string()],
+        vec!["C".to_string(), "D".to_string()],
+    ];
+    let change =
+        |code: &str, from, to| Change { code: code.to_string(), from, to };
+
+    // C can simply move to the next semester that offers it
+    let disruption = Disruption::Remove("C".to_string(), 1);
+    let repair = solver.repair(&plan, &disruption).unwrap().unwrap();
+    assert_eq!(repair.changes, vec![change("C", Some(1), Some(4))]);
+
+    // B is retaken as soon as it is offered again, which pushes D back
+    let disruption = Disruption::Remove("B".to_string(), 0);
+    let repair = solver.repair(&plan, &disruption).unwrap().unwrap();
+    assert_eq!(
+        repair.changes,
+        vec![change("B", Some(0), Some(1)), change("D", Some(1), Some(4))]
+    );
+
+    // nothing can replace a prerequisite that is gone
+    let disruption = Disruption::NotOffered("A".to_string());
+    assert!(solver.repair(&plan, &disruption).unwrap().is_none());
+
+    // the first semester has room for C, but it is already over
+    query.max_mods = 3;
+    let solver = Solver::new(&query).unwrap();
+    let disruption = Disruption::Remove("C".to_string(), 1);
+    let repair = solver.repair(&plan, &disruption).unwrap().unwrap();
+    assert_eq!(repair.changes, vec![change("C", Some(1), Some(4))]);
+}
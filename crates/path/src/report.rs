@@ -0,0 +1,60 @@
+use crate::Path;
+use std::collections::HashMap;
+use std::fmt;
+use types::{Module, Workload};
+
+/// Total weekly workload hours of the modules in one semester.
+pub(crate) fn sem_hours(sem: &[String], hours: &HashMap<String, f32>) -> f32 {
+    sem.iter().map(|code| hours.get(code).unwrap_or(&0.0)).sum()
+}
+
+/// Human-readable summary of a path, with a workload breakdown per semester.
+pub struct Report<'a> {
+    path: &'a Path,
+    workloads: HashMap<String, Workload>,
+}
+
+impl<'a> Report<'a> {
+    pub fn new(path: &'a Path, sample_space: &[Module]) -> Self {
+        let workloads = sample_space
+            .iter()
+            .map(|m| (m.to_code(), m.workload().unwrap_or_default()))
+            .collect();
+        Self { path, workloads }
+    }
+
+    /// Weekly workload of each semester in the path, by kind of class.
+    /// Modules whose workload can't be read count as none.
+    pub fn workloads(&self) -> Vec<Workload> {
+        let workload = |code: &String| self.workloads.get(code).copied();
+        let sem = |sem: &Vec<String>| sem.iter().filter_map(workload).sum();
+        self.path.record().iter().map(sem).collect()
+    }
+
+    /// Weekly workload hours of each semester in the path.
+    pub fn hours(&self) -> Vec<f32> {
+        self.workloads().iter().map(|w| w.total()).collect()
+    }
+
+    /// Weekly workload hours of the heaviest semester in the path.
+    pub fn peak_hours(&self) -> f32 {
+        self.hours().into_iter().fold(0.0, f32::max)
+    }
+}
+
+impl fmt::Display for Report<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let record = self.path.record();
+        for (i, (sem, hours)) in record.iter().zip(self.hours()).enumerate() {
+            let label = format!("Y{}S{}", i / 4 + 1, i % 4 + 1);
+            writeln!(f, "{label} [{hours:>5.1} h/week] {}", sem.join(", "))?;
+        }
+        write!(
+            f,
+            "{} semesters, {} modules, peak {:.1} h/week",
+            self.path.len(),
+            self.path.mod_count(),
+            self.peak_hours()
+        )
+    }
+}
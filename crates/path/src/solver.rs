@@ -0,0 +1,454 @@
+use crate::{Objective, Path, Query, Search};
+use microlp::{
+    ComparisonOp, OptimizationDirection, Problem, Solution, Variable,
+};
+use prereqtree::{Condition, PrereqTree};
+use std::collections::{HashMap, HashSet};
+use types::{Credits, Error, Result, Semester};
+
+/// What one solve of the model minimises.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Goal {
+    /// Number of semesters used.
+    Length,
+    /// Weekly workload hours of the heaviest semester.
+    Peak,
+    /// Number of modules taken.
+    Count,
+    /// Number of semester assignments that differ from `Solver::plan`.
+    Changes,
+    /// Total cost under the query's `Preferences`.
+    Preference,
+}
+
+/// Semester assignment written as an integer program.
+///
+/// `x[(m, s)]` is 1 if module `m` is taken in semester `s`, and only exists
+/// if `m` is offered in that semester. Prerequisite trees are encoded with
+/// one variable per tree node per semester, bounded above by the variables of
+/// its children, so that a module can only be taken once its tree is
+/// satisfied by the modules taken in earlier semesters.
+struct Model {
+    problem: Problem,
+    x: HashMap<(usize, usize), Variable>,
+    len: Variable,
+}
+
+/// Exact planner backend that solves a `Query` as an integer program instead
+/// of searching through paths. It takes the same input and gives the same
+/// output as `Planner`, so the two can be compared.
+pub struct Solver<'a> {
+    pub(crate) query: &'a Query,
+    pub(crate) index: HashMap<&'a str, usize>,
+    /// Credits of each module, by its index in the sample space.
+    pub(crate) credits: Vec<Credits>,
+    /// Semester assignments of an existing plan, as `(module, semester)`.
+    pub(crate) plan: HashSet<(usize, usize)>,
+    /// Semester assignments that may not be used.
+    pub(crate) banned: HashSet<(usize, usize)>,
+    /// Number of leading semesters that must keep exactly the assignments
+    /// of `plan`, because they are already over.
+    pub(crate) frozen: usize,
+}
+
+impl<'a> Solver<'a> {
+    /// Creates a solver for a query, or an error if the credits of a module
+    /// in its sample space can't be read.
+    pub fn new(query: &'a Query) -> Result<Self> {
+        let index = query
+            .sample_space
+            .iter()
+            .enumerate()
+            .map(|(i, m)| (m.code().as_str(), i))
+            .collect();
+        let credits = query.sample_space.iter().map(|m| m.credits());
+        Ok(Self {
+            query,
+            index,
+            credits: credits.collect::<Result<_>>()?,
+            plan: HashSet::new(),
+            banned: HashSet::new(),
+            frozen: 0,
+        })
+    }
+
+    /// Finds one optimal path. Ties on the query's objective are broken by
+    /// fewest modules for `Objective::Semesters`, and by fewest semesters for
+    /// `Objective::PeakWorkload`. Returns no routes if the query can't be
+    /// satisfied, and an error if the solver itself fails.
+    pub fn solve(&self) -> Result<Search> {
+        let (first, second) = match self.query.objective {
+            Objective::Semesters => (Goal::Length, Goal::Count),
+            Objective::PeakWorkload => (Goal::Peak, Goal::Length),
+            Objective::Preferences => (Goal::Preference, Goal::Length),
+        };
+        let routes = self.lexicographic(first, second)?.into_iter().collect();
+        Ok(Search { routes, expanded: 0, generated: 0 })
+    }
+
+    /// Finds a path that minimises `first`, and then `second` among the
+    /// paths that do.
+    pub(crate) fn lexicographic(
+        &self,
+        first: Goal,
+        second: Goal,
+    ) -> Result<Option<Path>> {
+        let Some(best) = self.optimum(first)? else {
+            return Ok(None);
+        };
+        match self.build(second, Some((first, best))) {
+            Some(model) => self.extract(model),
+            None => Ok(None),
+        }
+    }
+
+    /// Optimal value of `goal`, if the model can be satisfied at all.
+    fn optimum(&self, goal: Goal) -> Result<Option<f64>> {
+        let Some(model) = self.build(goal, None) else {
+            return Ok(None);
+        };
+        Ok(solve(&model.problem)?.map(|s| s.objective()))
+    }
+
+    /// Solves a model and reads the path out of its solution.
+    fn extract(&self, model: Model) -> Result<Option<Path>> {
+        let Some(solution) = solve(&model.problem)? else {
+            return Ok(None);
+        };
+        let is_taken = |m: usize, sem: usize| {
+            let var = model.x.get(&(m, sem));
+            var.is_some_and(|v| solution.var_value_rounded(*v) > 0.5)
+        };
+        let modules = &self.query.sample_space;
+        let len = solution.var_value(model.len).round() as usize;
+        let mut path = Path::new();
+        for sem in 0..len {
+            if let Some((_, external)) = self.blocked(sem) {
+                path.skip_sem(external);
+                continue;
+            }
+            let mut codes: Vec<&String> = (0..modules.len())
+                .filter(|m| is_taken(*m, sem))
+                .map(|m| modules[m].code())
+                .collect();
+            codes.sort();
+            codes.into_iter().for_each(|code| path.mark(code));
+            path.next_sem();
+        }
+        Ok(Some(path))
+    }
+
+    /// Builds the model that minimises `goal`. If `cap` is given, the model
+    /// also keeps that goal at or below a known optimum. Returns `None` if a
+    /// required module can't be taken at all.
+    fn build(&self, goal: Goal, cap: Option<(Goal, f64)>) -> Option<Model> {
+        use ComparisonOp::*;
+        let query = self.query;
+        let (modules, sems) = (&query.sample_space, query.max_sems);
+        let weight = |g: Goal| if g == goal { 1.0 } else { 0.0 };
+        let mut problem = Problem::new(OptimizationDirection::Minimize);
+        let len = problem.add_var(weight(Goal::Length), (0.0, sems as f64));
+        let peak = problem.add_var(weight(Goal::Peak), (0.0, f64::INFINITY));
+
+        // weight of each assignment when counting modules, changes or costs
+        let prefs = &query.preferences;
+        let count = |g: Goal, key: &(usize, usize)| match g {
+            Goal::Count => 1.0,
+            Goal::Changes if self.plan.contains(key) => -1.0,
+            Goal::Changes => 1.0,
+            Goal::Preference => {
+                let (code, sem) =
+                    (modules[key.0].code(), Semester::of_index(key.1));
+                prefs.cost_in(code, sem).unwrap_or(0.0) as f64
+            }
+            _ => 0.0,
+        };
+        let mut x = HashMap::new();
+        for (m, module) in modules.iter().enumerate() {
+            for sem in 0..sems {
+                let key = (m, sem);
+                if sem < self.frozen {
+                    // what was taken stays taken, and nothing else is added
+                    if self.plan.contains(&key) && !self.banned.contains(&key) {
+                        let var = problem.add_binary_var(count(goal, &key));
+                        problem.add_constraint([(var, 1.0)], Eq, 1.0);
+                        x.insert(key, var);
+                    }
+                } else if module.semesters().contains(&Semester::of_index(sem))
+                    && !self.banned.contains(&key)
+                    && self.blocked(sem).is_none()
+                    && self.credited(module.code()).is_none()
+                    && prefs.allows(module.code())
+                {
+                    let var = problem.add_binary_var(count(goal, &key));
+                    x.insert(key, var);
+                }
+            }
+        }
+        let taken = |m: usize, sems: std::ops::Range<usize>| -> Vec<_> {
+            sems.filter_map(|s| x.get(&(m, s)).map(|v| (*v, 1.0))).collect()
+        };
+
+        for (m, module) in modules.iter().enumerate() {
+            // each module is taken at most once
+            problem.add_constraint(taken(m, 0..sems), Le, 1.0);
+            for sem in 0..sems {
+                let Some(var) = x.get(&(m, sem)).copied() else { continue };
+                // the path lasts until the last semester with a module
+                let sem_no = sem as f64 + 1.0;
+                problem.add_constraint([(len, 1.0), (var, -sem_no)], Ge, 0.0);
+                // prerequisites are done in earlier semesters
+                let tree = module.prereqtree();
+                if let Some(sat) = self.satisfied(&mut problem, &x, tree, sem) {
+                    problem.add_constraint([(var, 1.0), (sat, -1.0)], Le, 0.0);
+                }
+            }
+        }
+
+        for code in query.targets.iter() {
+            if self.credited(code).is_some() {
+                continue;
+            }
+            let m = *self.index.get(code.as_str())?;
+            problem.add_constraint(taken(m, 0..sems), Eq, 1.0);
+        }
+        for pool in query.pools.iter() {
+            let (mut members, mut credits) = (vec![], vec![]);
+            let (mut count, mut total) = (0.0, 0.0);
+            for code in pool.modules.iter() {
+                let m = self.index.get(code.as_str()).copied();
+                let module_credits = m.map_or(0.0, |m| self.credits[m].min);
+                if self.credited(code).is_some() {
+                    count += 1.0;
+                    total += module_credits as f64;
+                    continue;
+                }
+                let Some(m) = m else { continue };
+                for (var, _) in taken(m, 0..sems) {
+                    members.push((var, 1.0));
+                    credits.push((var, module_credits as f64));
+                }
+            }
+            problem.add_constraint(members, Ge, pool.count as f64 - count);
+            if let Some(min) = pool.credits {
+                problem.add_constraint(credits, Ge, min as f64 - total);
+            }
+        }
+        for (code, sem) in query.deadlines.iter() {
+            if self.credited(code).is_some_and(|s| s <= *sem) {
+                continue;
+            }
+            let m = *self.index.get(code.as_str())?;
+            problem.add_constraint(taken(m, 0..sems.min(sem + 1)), Eq, 1.0);
+        }
+        for (code, sem) in query.pinned.iter() {
+            let m = *self.index.get(code.as_str())?;
+            problem.add_constraint([(*x.get(&(m, *sem))?, 1.0)], Eq, 1.0);
+        }
+
+        for sem in 0..sems {
+            let doing: Vec<(usize, Variable)> = (0..modules.len())
+                .filter_map(|m| x.get(&(m, sem)).map(|v| (m, *v)))
+                .collect();
+            let load = doing.iter().map(|(_, v)| (*v, 1.0));
+            problem.add_constraint(load, Le, query.max_mods as f64);
+            let mut hours: Vec<_> = doing
+                .iter()
+                .map(|(m, v)| (*v, modules[*m].workload_hours() as f64))
+                .collect();
+            if let Some(max) = query.max_hours {
+                problem.add_constraint(hours.clone(), Le, max as f64);
+            }
+            if let Some(max) = query.max_credits {
+                let credits = doing
+                    .iter()
+                    .map(|(m, v)| (*v, self.credits[*m].max as f64));
+                problem.add_constraint(credits, Le, max as f64);
+            }
+            hours.push((peak, -1.0));
+            problem.add_constraint(hours, Le, 0.0);
+            if query.allow_exam_clashes {
+                continue;
+            }
+            // modules whose exams clash are not taken together
+            let offered = Semester::of_index(sem);
+            for (i, (a, x_a)) in doing.iter().enumerate() {
+                for (b, x_b) in &doing[i + 1..] {
+                    if modules[*a].exam_clashes_with(&modules[*b], offered) {
+                        let pair = [(*x_a, 1.0), (*x_b, 1.0)];
+                        problem.add_constraint(pair, Le, 1.0);
+                    }
+                }
+            }
+        }
+
+        if let Some((goal, best)) = cap {
+            let terms = match goal {
+                Goal::Length => vec![(len, 1.0)],
+                Goal::Peak => vec![(peak, 1.0)],
+                Goal::Count | Goal::Changes | Goal::Preference => {
+                    x.iter().map(|(k, v)| (*v, count(goal, k))).collect()
+                }
+            };
+            problem.add_constraint(terms, Le, best + 1e-6);
+        }
+        Some(Model { problem, x, len })
+    }
+
+    /// Blocked semester, and the modules credited externally in it.
+    fn blocked(&self, sem: usize) -> Option<&(usize, Vec<String>)> {
+        self.query.blocked.iter().find(|(s, _)| *s == sem)
+    }
+
+    /// Blocked semester in which a module is credited externally, if any.
+    fn credited(&self, code: &str) -> Option<usize> {
+        let mut blocked = self.query.blocked.iter();
+        let credited = blocked.find(|(_, ext)| ext.iter().any(|c| c == code));
+        credited.map(|(sem, _)| *sem)
+    }
+
+    /// Variable that can only be 1 if `tree` is satisfied by the modules
+    /// taken before `sem`. Returns `None` if the tree is always satisfied.
+    fn satisfied(
+        &self,
+        problem: &mut Problem,
+        x: &HashMap<(usize, usize), Variable>,
+        tree: &PrereqTree,
+        sem: usize,
+    ) -> Option<Variable> {
+        use PrereqTree::*;
+        match tree {
+            Only(code) if code.is_empty() => None,
+            Only(code) if self.credited(code).is_some_and(|s| s < sem) => None,
+            Only(code) => {
+                let done = match self.index.get(code.as_str()) {
+                    Some(m) => (0..sem)
+                        .filter_map(|s| x.get(&(*m, s)))
+                        .copied()
+                        .collect(),
+                    None => vec![],
+                };
+                Some(at_most_sum(problem, &done))
+            }
+            And { and } => {
+                let children: Vec<_> = and
+                    .iter()
+                    .filter_map(|t| self.satisfied(problem, x, t, sem))
+                    .collect();
+                match children.is_empty() {
+                    true => None,
+                    false => Some(at_most_each(problem, &children)),
+                }
+            }
+            Or { or } => {
+                let mut children = vec![];
+                for t in or {
+                    children.push(self.satisfied(problem, x, t, sem)?);
+                }
+                match children.is_empty() {
+                    true => None,
+                    false => Some(at_most_sum(problem, &children)),
+                }
+            }
+            Cond(Condition::Credits(min)) => {
+                let profile = self.query.profile_at(sem, 0.0);
+                let mut done = vec![];
+                let mut credited = profile.credits as f64;
+                for (m, module) in self.query.sample_space.iter().enumerate() {
+                    let credits = self.credits[m].min as f64;
+                    if self.credited(module.code()).is_some_and(|s| s < sem) {
+                        credited += credits;
+                    }
+                    let taken = (0..sem).filter_map(|s| x.get(&(m, s)));
+                    done.extend(taken.map(|v| (*v, -credits)));
+                }
+                if credited >= *min as f64 {
+                    return None;
+                }
+                // the condition can only be 1 once `min` MCs are completed
+                let var = problem.add_var(0.0, (0.0, 1.0));
+                done.push((var, *min as f64));
+                problem.add_constraint(done, ComparisonOp::Le, credited);
+                Some(var)
+            }
+            Cond(cond) => {
+                match cond.satisfied_by(&self.query.profile_at(sem, 0.0)) {
+                    true => None,
+                    false => Some(problem.add_var(0.0, (0.0, 0.0))),
+                }
+            }
+        }
+    }
+}
+
+/// New variable in [0, 1] that is at most each of `terms`.
+fn at_most_each(problem: &mut Problem, terms: &[Variable]) -> Variable {
+    let var = problem.add_var(0.0, (0.0, 1.0));
+    for t in terms {
+        problem.add_constraint([(var, 1.0), (*t, -1.0)], ComparisonOp::Le, 0.0);
+    }
+    var
+}
+
+/// New variable in [0, 1] that is at most the sum of `terms`.
+fn at_most_sum(problem: &mut Problem, terms: &[Variable]) -> Variable {
+    let var = problem.add_var(0.0, (0.0, 1.0));
+    let mut sum: Vec<_> = terms.iter().map(|t| (*t, -1.0)).collect();
+    sum.push((var, 1.0));
+    problem.add_constraint(sum, ComparisonOp::Le, 0.0);
+    var
+}
+
+/// Solves a problem. Returns `None` if it can't be satisfied, and an error if
+/// the solver fails for any other reason.
+fn solve(problem: &Problem) -> Result<Option<Solution>> {
+    match problem.solve() {
+        Ok(solution) => Ok(Some(solution)),
+        Err(microlp::Error::Infeasible) => Ok(None),
+        Err(e) => Err(Error::SolverFailed(e.to_string())),
+    }
+}
+
+#[test]
+fn solver_test() {
+    use crate::test_utils::module;
+    use crate::Planner;
+    let sample_space = vec![
+        module("A", &[1, 2], "", &[6.0]),
+        module("B", &[1, 2], "", &[6.0]),
+        module("C", &[2], "", &[2.0]),
+        module("D", &[1, 2], r#"{ "or": ["A", "C"] }"#, &[4.0]),
+        module("E", &[1, 2], r#"{ "and": ["B", "D"] }"#, &[4.0]),
+    ];
+    let mut query = Query::new(sample_space, vec!["E".to_string()]);
+    use Objective::*;
+    for objective in [Semesters, PeakWorkload, Preferences] {
+        query.objective = objective;
+        let planner = Planner::new(&query).unwrap();
+        let cost = |p: &Path| match objective {
+            Objective::Semesters => p.len() as f32,
+            Objective::PeakWorkload => {
+                planner.hours(p).into_iter().fold(0.0, f32::max)
+            }
+            Objective::Preferences => planner.preference_cost(p),
+        };
+        let solved = Solver::new(&query).unwrap().solve().unwrap().routes;
+        let searched = planner.search().routes;
+        assert_eq!(cost(&solved[0]), cost(&searched[0]));
+    }
+
+    // pinned modules and deadlines are kept
+    query.objective = Objective::Semesters;
+    query.pinned = vec![("B".to_string(), 1)];
+    query.deadlines = vec![("C".to_string(), 1)];
+    let path = &Solver::new(&query).unwrap().solve().unwrap().routes[0];
+    assert!(path.record()[1].contains(&"B".to_string()));
+    assert!(path.record()[1].contains(&"C".to_string()));
+    let searched = Planner::new(&query).unwrap().search().routes;
+    assert_eq!(path.len(), searched[0].len());
+
+    // C is only offered in semester 2
+    query.pinned = vec![("C".to_string(), 0)];
+    assert!(Solver::new(&query).unwrap().solve().unwrap().routes.is_empty());
+    assert!(Planner::new(&query).unwrap().search().routes.is_empty());
+}
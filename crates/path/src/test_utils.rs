@@ -0,0 +1,26 @@
+use types::{Module, Semester};
+
+/// Builds a module offered in `sems`, with a prereqtree given as NUSMods JSON.
+pub fn module(
+    code: &str,
+    sems: &[usize],
+    tree: &str,
+    workload: &[f32],
+) -> Module {
+    let tree = match tree {
+        "" => String::from(r#""""#),
+        tree => tree.to_string(),
+    };
+    let json = format!(
+        r#"{{ "moduleCode": "{code}", "moduleCredit": "4", "prereqTree": {tree}, "workload": {workload:?} }}"#
+    );
+    let mut module =
+        Module::from(serde_json::from_str::<nusmods::Module>(&json).unwrap());
+    module.set_semesters(semesters(sems));
+    module
+}
+
+/// Reads semester numbers, in [1, 4].
+pub fn semesters(sems: &[usize]) -> Vec<Semester> {
+    sems.iter().map(|v| Semester::new(*v).unwrap()).collect()
+}
@@ -0,0 +1,133 @@
+use crate::{Path, Planner};
+use std::collections::BTreeMap;
+use types::{Lesson, Module, Semester};
+
+/// Class picked for one lesson type of a module, such as tutorial group 3.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Slot {
+    pub code: String,
+    pub lesson_type: String,
+    pub class_no: String,
+}
+
+/// A lesson type of a module, and its classes with their lessons.
+type Choice<'a> = (Slot, Vec<(String, Vec<&'a Lesson>)>);
+
+/// Picks a class for every lesson type of every module, such that no two
+/// lessons clash, in semester `sem`. Returns `None` if there is
+/// no such choice. Modules without lessons are always fine.
+pub fn timetable(modules: &[&Module], sem: Semester) -> Option<Vec<Slot>> {
+    // the classes to choose from for each module and lesson type
+    let mut choices: Vec<Choice> = vec![];
+    for module in modules {
+        let mut by_type: BTreeMap<&str, BTreeMap<&str, Vec<&Lesson>>> =
+            BTreeMap::new();
+        for lesson in module.lessons(sem) {
+            let classes = by_type.entry(&lesson.lesson_type).or_default();
+            classes.entry(&lesson.class_no).or_default().push(lesson);
+        }
+        for (lesson_type, classes) in by_type {
+            let slot = Slot {
+                code: module.to_code(),
+                lesson_type: lesson_type.to_string(),
+                class_no: String::new(),
+            };
+            let classes = classes.into_iter().map(|(k, v)| (k.to_string(), v));
+            choices.push((slot, classes.collect()));
+        }
+    }
+    // fewest classes first, so that dead ends are found early
+    choices.sort_by_key(|(_, classes)| classes.len());
+    let mut taken = vec![];
+    let mut slots = vec![];
+    pick(&choices, &mut taken, &mut slots).then_some(slots)
+}
+
+/// Picks a class for each of `choices` in turn, backtracking on clashes.
+fn pick<'a>(
+    choices: &'a [Choice<'a>],
+    taken: &mut Vec<&'a Lesson>,
+    slots: &mut Vec<Slot>,
+) -> bool {
+    let Some(((slot, classes), rest)) = choices.split_first() else {
+        return true;
+    };
+    for (class_no, lessons) in classes {
+        let clash = |l: &&Lesson| taken.iter().any(|t| t.clashes_with(l));
+        if lessons.iter().any(clash) {
+            continue;
+        }
+        let len = taken.len();
+        taken.extend(lessons.iter().copied());
+        slots.push(Slot { class_no: class_no.to_string(), ..slot.clone() });
+        if pick(rest, taken, slots) {
+            return true;
+        }
+        taken.truncate(len);
+        slots.pop();
+    }
+    false
+}
+
+impl Planner<'_> {
+    /// Lesson slots without clashes for the semester in progress of a path,
+    /// if there are any.
+    pub fn timetable(&self, path: &Path) -> Option<Vec<Slot>> {
+        let doing = path.doing().iter();
+        let doing: Vec<_> = doing
+            .filter_map(|c| self.modules.get(c.as_str()).copied())
+            .collect();
+        timetable(&doing, path.sem())
+    }
+}
+
+#[test]
+fn timetable_test() {
+    use crate::test_utils::module;
+    use crate::{Query, Violation};
+    use types::Weeks;
+    let sem = |n| Semester::new(n).unwrap();
+    let lesson = |lesson_type: &str, class_no: &str, day: &str, start: &str| {
+        let hour: usize = start[..2].parse().unwrap();
+        Lesson {
+            semester: sem(1),
+            class_no: class_no.to_string(),
+            lesson_type: lesson_type.to_string(),
+            day: day.to_string(),
+            start_time: start.to_string(),
+            end_time: format!("{:02}00", hour + 2),
+            weeks: Weeks::Numbers((1..=13).collect()),
+        }
+    };
+    let with_lessons = |code: &str, lessons: Vec<Lesson>| {
+        let mut m = module(code, &[1], "", &[]);
+        m.set_lessons(lessons);
+        m
+    };
+    let sample_space = vec![
+        with_lessons("A", vec![lesson("Lecture", "1", "Monday", "1000")]),
+        with_lessons("B", vec![lesson("Lecture", "1", "Monday", "1100")]),
+        with_lessons(
+            "C",
+            vec![
+                lesson("Tutorial", "1", "Monday", "1000"),
+                lesson("Tutorial", "2", "Tuesday", "1000"),
+            ],
+        ),
+    ];
+    let [a, b, c] = [&sample_space[0], &sample_space[1], &sample_space[2]];
+    assert_eq!(timetable(&[a, b], sem(1)), None);
+    let slots = timetable(&[a, c], sem(1)).unwrap();
+    assert_eq!(slots.iter().find(|s| s.code == "C").unwrap().class_no, "2");
+    assert_eq!(timetable(&[a, b], sem(2)), Some(vec![]));
+
+    // A and B can't be taken in the same semester
+    let targets = ["A", "B"].map(|c| c.to_string()).to_vec();
+    let plan = [targets.clone()];
+    let mut query = Query::new(sample_space, targets);
+    query.check_timetable = true;
+    let planner = Planner::new(&query).unwrap();
+    assert_eq!(planner.search().routes[0].len(), 5);
+    let issues = planner.validate(&plan);
+    assert_eq!(issues[0].violation, Violation::TimetableClash);
+}
@@ -0,0 +1,258 @@
+use crate::report::sem_hours;
+use crate::timetable::timetable;
+use crate::Planner;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use types::{ModuleCode, Semester};
+
+/// Something wrong with one semester of a plan.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Violation {
+    /// The module is not in the query's sample space, so nothing about it
+    /// can be checked.
+    NotInSampleSpace(String),
+    /// The module was already taken in the given semester.
+    Duplicate(String, usize),
+    /// The module is not offered in this semester.
+    NotOffered(String),
+    /// The module's prerequisites are not done in earlier semesters. Holds
+    /// the fewest modules still needed to meet them.
+    UnmetPrerequisites(String, u8),
+    /// The module needs a higher year of study, more MCs completed or a
+    /// different programme.
+    UnmetConditions(String),
+    /// The first module is precluded by the second, taken earlier in the
+    /// plan or in the same semester. Variants of one module, such as CS1010
+    /// and CS1010S, preclude each other.
+    Precluded(String, String),
+    /// The exams of the two modules overlap.
+    ExamClash(String, String),
+    /// No choice of classes lets every module in the semester be attended
+    /// without clashes.
+    TimetableClash,
+    /// The semester has more modules than the query allows.
+    TooManyModules(usize),
+    /// The semester has more weekly workload hours than the query allows.
+    TooManyHours(f32),
+    /// The semester has more modular credits than the query allows.
+    TooManyCredits(f32),
+}
+
+/// A violation, and the semester of the plan where it happens, as an index
+/// into the plan.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Issue {
+    pub sem: usize,
+    pub violation: Violation,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use Violation::*;
+        let sem = |i: usize| format!("Y{}S{}", i / 4 + 1, i % 4 + 1);
+        write!(f, "{}: ", sem(self.sem))?;
+        match &self.violation {
+            NotInSampleSpace(code) => {
+                write!(f, "{code} is not in the query's sample space")
+            }
+            Duplicate(code, i) => {
+                write!(f, "{code} was already taken in {}", sem(*i))
+            }
+            NotOffered(code) => write!(f, "{code} is not offered"),
+            UnmetPrerequisites(code, left) => write!(
+                f,
+                "{code} needs {left} more module(s) to meet its prerequisites"
+            ),
+            UnmetConditions(code) => {
+                write!(f, "{code} has year, credit or programme conditions")
+            }
+            Precluded(code, by) => write!(f, "{code} is precluded by {by}"),
+            ExamClash(a, b) => write!(f, "{a} and {b} have exams that clash"),
+            TimetableClash => write!(f, "no timetable without clashes"),
+            TooManyModules(n) => write!(f, "{n} modules is over the limit"),
+            TooManyHours(h) => write!(f, "{h:.1} h/week is over the limit"),
+            TooManyCredits(c) => write!(f, "{c} MCs is over the limit"),
+        }
+    }
+}
+
+impl Planner<'_> {
+    /// Checks a plan written by hand, in the same shape as `Path::record`,
+    /// against the query's modules and limits. Returns every violation
+    /// found, in order of semester.
+    pub fn validate(&self, plan: &[Vec<String>]) -> Vec<Issue> {
+        let mut issues = vec![];
+        let preclusions: HashMap<&str, Vec<String>> =
+            self.modules.iter().map(|(k, m)| (*k, m.preclusions())).collect();
+        let precludes = |a: &str, b: &str| {
+            preclusions.get(a).is_some_and(|p| p.iter().any(|v| v == b))
+        };
+        let parse = |code: &str| code.parse::<ModuleCode>().ok();
+        let variants = |a: &str, b: &str| {
+            parse(a).zip(parse(b)).is_some_and(|(a, b)| a.same_base(&b))
+        };
+        // modules done before the semester being checked
+        let mut done = HashSet::new();
+        let mut taken: HashMap<&String, usize> = HashMap::new();
+
+        for (sem, codes) in plan.iter().enumerate() {
+            let profile = self.profile(sem, &done);
+            let offered = Semester::of_index(sem);
+            let mut issue = |violation| issues.push(Issue { sem, violation });
+            if codes.len() > self.query.max_mods {
+                issue(Violation::TooManyModules(codes.len()));
+            }
+            let hours = sem_hours(codes, &self.hours);
+            if self.query.max_hours.is_some_and(|max| hours > max) {
+                issue(Violation::TooManyHours(hours));
+            }
+            let most = codes.iter().filter_map(|c| self.credits.get(c));
+            let most: f32 = most.map(|c| c.max).sum();
+            if self.query.max_credits.is_some_and(|max| most > max) {
+                issue(Violation::TooManyCredits(most));
+            }
+            for code in codes {
+                if let Some(first) = taken.get(code) {
+                    issue(Violation::Duplicate(code.to_string(), *first));
+                    continue;
+                }
+                let mut prevs: Vec<_> = taken.keys().collect();
+                prevs.sort();
+                for prev in prevs {
+                    if precludes(code, prev)
+                        || precludes(prev, code)
+                        || variants(code, prev)
+                    {
+                        let by = prev.to_string();
+                        issue(Violation::Precluded(code.to_string(), by));
+                    }
+                }
+                taken.insert(code, sem);
+                let Some(module) = self.modules.get(code.as_str()) else {
+                    issue(Violation::NotInSampleSpace(code.to_string()));
+                    continue;
+                };
+                if !module.semesters().contains(&offered) {
+                    issue(Violation::NotOffered(code.to_string()));
+                }
+                let tree = module.prereqtree();
+                if !tree.satisfied_by(&done) {
+                    let left = tree.left_to_unlock(&done);
+                    let code = code.to_string();
+                    issue(Violation::UnmetPrerequisites(code, left));
+                } else if !tree.satisfied_with(&done, &profile) {
+                    issue(Violation::UnmetConditions(code.to_string()));
+                }
+            }
+            let modules: Vec<_> = codes
+                .iter()
+                .filter_map(|c| self.modules.get(c.as_str()).copied())
+                .collect();
+            for (i, a) in modules.iter().enumerate() {
+                for b in &modules[i + 1..] {
+                    if a.exam_clashes_with(b, offered) {
+                        let (a, b) = (a.to_code(), b.to_code());
+                        issue(Violation::ExamClash(a, b));
+                    }
+                }
+            }
+            if timetable(&modules, offered).is_none() {
+                issue(Violation::TimetableClash);
+            }
+            done.extend(codes.iter().cloned());
+        }
+        issues
+    }
+}
+
+#[test]
+fn validate_test() {
+    use crate::test_utils::{module, semesters};
+    use crate::Query;
+    use types::Module;
+    let json = r#"{
+        "moduleCode": "MA1102R",
+        "moduleCredit": "4",
+        "preclusion": "MA1521/MA1505"
+    }"#;
+    let mut precluded =
+        Module::from(serde_json::from_str::<nusmods::Module>(json).unwrap());
+    precluded.set_semesters(semesters(&[1, 2]));
+    let sample_space = vec![
+        module("A", &[1, 2], "", &[6.0]),
+        module("B", &[1, 2], "", &[6.0]),
+        module("C", &[2], r#"{ "and": ["A", "B"] }"#, &[6.0]),
+        module("MA1521", &[1, 2], "", &[]),
+        module("MA1102", &[1, 2], "", &[]),
+        precluded,
+    ];
+    let mut query = Query::new(sample_space, vec![]);
+    query.max_mods = 2;
+    query.max_hours = Some(10.0);
+    let planner = Planner::new(&query).unwrap();
+    let plan = |sems: &[&[&str]]| -> Vec<Vec<String>> {
+        sems.iter()
+            .map(|sem| sem.iter().map(|code| code.to_string()).collect())
+            .collect()
+    };
+
+    assert!(planner.validate(&plan(&[&["A"], &["B"]])).is_empty());
+
+    let issues = planner.validate(&plan(&[
+        &["A", "B", "C"],
+        &["A", "MA1521"],
+        &["X"],
+        &[],
+        &["MA1102R"],
+        &["MA1102"],
+    ]));
+    use Violation::*;
+    let s = |v: &str| v.to_string();
+    assert_eq!(
+        issues.into_iter().map(|i| (i.sem, i.violation)).collect::<Vec<_>>(),
+        vec![
+            (0, TooManyModules(3)),
+            (0, TooManyHours(18.0)),
+            (0, NotOffered(s("C"))),
+            (0, UnmetPrerequisites(s("C"), 2)),
+            (1, Duplicate(s("A"), 0)),
+            (2, NotInSampleSpace(s("X"))),
+            (4, Precluded(s("MA1102R"), s("MA1521"))),
+            (5, Precluded(s("MA1102"), s("MA1102R"))),
+        ]
+    );
+}
+
+#[test]
+fn credits_test() {
+    use crate::test_utils::module;
+    use crate::{Query, Solver};
+    use types::{Credits, Module};
+    let json = r#"{ "moduleCode": "C", "moduleCredit": "four" }"#;
+    let unreadable =
+        Module::from(serde_json::from_str::<nusmods::Module>(json).unwrap());
+    let mut variable = module("B", &[1, 2], "", &[]);
+    variable.set_credits(Credits { min: 4.0, max: 8.0 });
+    let mut sample_space =
+        vec![module("A", &[1, 2], "", &[]), variable, unreadable];
+    let targets = vec!["A".to_string(), "B".to_string()];
+    let mut query = Query::new(sample_space.clone(), targets.clone());
+    query.max_credits = Some(8.0);
+
+    // C's credits can't be read, so it can't be planned with
+    assert!(Planner::new(&query).is_err());
+    assert!(Solver::new(&query).is_err());
+    sample_space.pop();
+    query.sample_space = sample_space;
+
+    // B may be worth 8 MCs, so it can't be taken with A
+    let planner = Planner::new(&query).unwrap();
+    assert_eq!(planner.search().routes[0].len(), 2);
+    assert_eq!(
+        Solver::new(&query).unwrap().solve().unwrap().routes[0].len(),
+        2
+    );
+    let issues = planner.validate(&[targets]);
+    let violations: Vec<_> = issues.into_iter().map(|i| i.violation).collect();
+    assert_eq!(violations, [Violation::TooManyCredits(12.0)]);
+}
@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+
+/// A prerequisite that is not a module, written in JSON as one of
+/// `{ "year": 3 }`, `{ "credits": 80 }` or `{ "programme": [...] }`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Condition {
+    /// Minimum year of study.
+    Year(u8),
+    /// Minimum number of MCs completed.
+    Credits(f32),
+    /// Membership in at least one of these programmes. Only checked against
+    /// a student whose programmes are known.
+    Programme(Vec<String>),
+}
+
+/// The student that a `Condition` is checked against.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Profile {
+    /// Year of study.
+    pub year: u8,
+    /// MCs completed.
+    pub credits: f32,
+    /// Programmes that the student is enrolled in. If none are given, the
+    /// student's programme is unknown, and programme restrictions, which are
+    /// only guessed from the text of a module's prerequisites, are taken to
+    /// be met.
+    pub programmes: Vec<String>,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self { year: 1, credits: 0.0, programmes: vec![] }
+    }
+}
+
+impl Condition {
+    /// Checks if a student meets the condition.
+    pub fn satisfied_by(&self, profile: &Profile) -> bool {
+        match self {
+            Self::Year(year) => profile.year >= *year,
+            Self::Credits(credits) => profile.credits >= *credits,
+            Self::Programme(list) => {
+                profile.programmes.is_empty()
+                    || list.iter().any(|p| profile.programmes.contains(p))
+            }
+        }
+    }
+
+    /// Picks out the conditions that NUSMods gives in the text of a module's
+    /// prerequisites, such as "Year 3 standing", "Completed 80 MCs" or "Only
+    /// for Computer Science students".
+    pub fn from_text(text: &str) -> Vec<Self> {
+        let words: Vec<String> = text
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(|w| w.to_lowercase())
+            .collect();
+        let mut conditions = vec![];
+        for w in words.windows(3) {
+            if let ("year", Ok(year), "standing") =
+                (w[0].as_str(), w[1].parse::<u8>(), w[2].as_str())
+            {
+                conditions.push(Self::Year(year));
+            }
+        }
+        let credits = (0..words.len()).filter_map(|i| credits_at(&words, i));
+        if let Some(most) = credits.reduce(f32::max) {
+            conditions.push(Self::Credits(most));
+        }
+        if let Some(list) = programmes(text) {
+            conditions.push(Self::Programme(list));
+        }
+        conditions
+    }
+}
+
+/// MCs that the words starting at `i` ask to have completed, as in
+/// "completed 80 MCs", "at least 80 units" or "80MCs". A number of MCs
+/// without such a word before it, as in "a 4 MC module", is not a condition.
+fn credits_at(words: &[String], i: usize) -> Option<f32> {
+    let word = words[i].as_str();
+    let digits = word.chars().take_while(|c| c.is_ascii_digit()).count();
+    let (number, unit) = match &word[digits..] {
+        "" => (word, words.get(i + 1)?.as_str()),
+        unit => (&word[..digits], unit),
+    };
+    let credits: f32 = number.parse().ok()?;
+    let is_unit = ["mc", "mcs", "unit", "units", "modular"].contains(&unit);
+    let before = &words[i.saturating_sub(3)..i];
+    let needs = ["completed", "completion", "least", "minimum", "passed"];
+    let is_needed = before.iter().any(|w| needs.contains(&w.as_str()));
+    (is_unit && is_needed).then_some(credits)
+}
+
+/// Programmes that a module is restricted to, as in "Only for Computer
+/// Science students" or "only for students in Business Analytics or
+/// Information Systems".
+fn programmes(text: &str) -> Option<Vec<String>> {
+    // ASCII lowercase keeps byte offsets, so `lower` can index into `text`
+    let lower = text.to_ascii_lowercase();
+    let start = lower.find("only for ")? + "only for ".len();
+    let (from, to) = match lower[start..].starts_with("students ") {
+        true => {
+            let mut from = start + "students ".len();
+            let preps = ["enrolled in ", "in ", "from ", "of "];
+            if let Some(p) =
+                preps.iter().find(|p| lower[from..].starts_with(*p))
+            {
+                from += p.len();
+            }
+            let end = lower[from..].find(['.', ';', '(']);
+            (from, end.map_or(lower.len(), |end| from + end))
+        }
+        false => (start, start + lower[start..].find(" students")?),
+    };
+    let names = text[from..to].trim();
+    let names = ["the ", "The "]
+        .iter()
+        .find_map(|p| names.strip_prefix(p))
+        .unwrap_or(names);
+    let list: Vec<String> = names
+        .split(", ")
+        .flat_map(|v| v.split(" and "))
+        .flat_map(|v| v.split(" or "))
+        .flat_map(|v| v.split('/'))
+        .map(|v| v.trim())
+        .map(|v| v.strip_suffix(" programme").unwrap_or(v))
+        .map(|v| v.strip_suffix(" majors").unwrap_or(v))
+        .map(|v| v.strip_suffix(" major").unwrap_or(v))
+        .filter(|v| !v.is_empty())
+        .map(|v| v.to_string())
+        .collect();
+    let is_name = |v: &String| {
+        let first = v.split(' ').next().unwrap_or("").to_ascii_lowercase();
+        v.chars().all(|c| c.is_ascii_alphabetic() || " &-.".contains(c))
+            && !["who", "with", "that", "taking"].contains(&first.as_str())
+    };
+    (!list.is_empty() && list.iter().all(is_name)).then_some(list)
+}
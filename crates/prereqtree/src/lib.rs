@@ -1,7 +1,9 @@
 #[macro_use]
 mod macros;
+mod condition;
 mod std_impl;
 
+pub use condition::{Condition, Profile};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::mem;
@@ -12,6 +14,7 @@ pub enum PrereqTree {
     Only(String),
     And { and: Vec<PrereqTree> },
     Or { or: Vec<PrereqTree> },
+    Cond(Condition),
 }
 use PrereqTree::*;
 type T = PrereqTree;
@@ -34,6 +37,7 @@ impl PrereqTree {
             Only(only) => only.eq(module_code),
             And { and } => and.iter().any(|v| v.contains_code(module_code)),
             Or { or } => or.iter().any(|v| v.contains_code(module_code)),
+            Cond(_) => false,
         }
     }
 
@@ -56,6 +60,7 @@ impl PrereqTree {
             Or { or } => Some(Self::Or {
                 or: or.iter().filter_map(|v| v.retain(keep)).collect(),
             }),
+            Cond(v) => Some(Cond(v.clone())),
         }
     }
 
@@ -70,6 +75,7 @@ impl PrereqTree {
             Or { or } => {
                 or.iter().map(|v| v.min_to_unlock()).min().unwrap_or(0)
             }
+            Cond(_) => 0,
         }
     }
 
@@ -83,10 +89,12 @@ impl PrereqTree {
             Or { or } => {
                 or.iter().map(|v| v.left_to_unlock(done)).min().unwrap_or(0)
             }
+            Cond(_) => 0,
         }
     }
 
-    /// Checks if a set of modules done satisfies the prereqtree.
+    /// Checks if a set of modules done satisfies the prereqtree. Conditions
+    /// that are not modules are assumed to be met.
     pub fn satisfied_by(&self, done: &HashSet<String>) -> bool {
         match self {
             Only(only) => only.eq("") || done.contains(only),
@@ -96,6 +104,36 @@ impl PrereqTree {
             Or { or } => {
                 or.iter().fold(or.is_empty(), |a, p| a || p.satisfied_by(done))
             }
+            Cond(_) => true,
+        }
+    }
+
+    /// Checks if a set of modules done satisfies the prereqtree, where
+    /// conditions that are not modules are checked against a student.
+    pub fn satisfied_with(
+        &self,
+        done: &HashSet<String>,
+        profile: &Profile,
+    ) -> bool {
+        match self {
+            Only(_) => self.satisfied_by(done),
+            And { and } => and.iter().all(|p| p.satisfied_with(done, profile)),
+            Or { or } => {
+                or.is_empty()
+                    || or.iter().any(|p| p.satisfied_with(done, profile))
+            }
+            Cond(condition) => condition.satisfied_by(profile),
+        }
+    }
+
+    /// Checks if the prereqtree has any conditions that are not modules.
+    pub fn has_conditions(&self) -> bool {
+        match self {
+            Only(_) => false,
+            And { and: t } | Or { or: t } => {
+                t.iter().any(|v| v.has_conditions())
+            }
+            Cond(_) => true,
         }
     }
 
@@ -114,6 +152,7 @@ impl PrereqTree {
                 .map(|v| v.min_path())
                 .min_by(|a, b| a.len().cmp(&b.len()))
                 .unwrap_or(vec![]),
+            Cond(_) => vec![],
         }
     }
 
@@ -130,6 +169,52 @@ impl PrereqTree {
             .min_by(|a, b| a.len().cmp(&b.len()))
     }
 
+    /// Returns one possible path with the lowest total cost, where `cost`
+    /// gives the cost of each module, or `None` if it must not be taken.
+    /// Returns `None` if the tree can't be satisfied without such a module.
+    pub fn min_path_by<F>(&self, cost: &F) -> Option<Vec<String>>
+    where
+        F: Fn(&str) -> Option<f32>,
+    {
+        match self {
+            Only(only) if only.eq("") => Some(vec![]),
+            Only(only) => cost(only).map(|_| vec![only.to_string()]),
+            And { and } => {
+                let mut set = HashSet::new();
+                for v in and {
+                    set.extend(v.min_path_by(cost)?);
+                }
+                Some(Vec::from_iter(set))
+            }
+            Or { or } if or.is_empty() => Some(vec![]),
+            Cond(_) => Some(vec![]),
+            Or { or } => {
+                or.iter().filter_map(|v| v.min_path_by(cost)).min_by(|a, b| {
+                    total_cost(a, cost).total_cmp(&total_cost(b, cost))
+                })
+            }
+        }
+    }
+
+    /// Same as `min_path_filtered`, but finds the path with the lowest total
+    /// cost, skipping every path with a module that must not be taken.
+    pub fn min_path_filtered_by<F>(
+        &self,
+        required: &Vec<String>,
+        cost: &F,
+    ) -> Option<Vec<String>>
+    where
+        F: Fn(&str) -> Option<f32>,
+    {
+        self.all_paths()
+            .into_iter()
+            // must contain all required modules
+            .filter(|p| required.iter().all(|r| p.contains(r)))
+            // must not contain any module that can't be taken
+            .filter(|p| p.iter().all(|v| cost(v).is_some()))
+            .min_by(|a, b| total_cost(a, cost).total_cmp(&total_cost(b, cost)))
+    }
+
     /// Returns every module found in the PrereqTree in a list.
     pub fn flatten(&self) -> Vec<String> {
         match self {
@@ -140,6 +225,7 @@ impl PrereqTree {
                 t.iter().for_each(|v| set.extend(v.flatten()));
                 Vec::from_iter(set)
             }
+            Cond(_) => vec![],
         }
     }
 
@@ -156,6 +242,8 @@ impl PrereqTree {
                 // cross-chains all children journeys into one.
                 util::weave(&t.iter().map(|st| st.all_paths()).collect())
             }
+            // met without taking any module
+            Cond(_) => vec![vec![]],
         }
     }
 
@@ -180,6 +268,7 @@ impl PrereqTree {
                     (len, or) if or.len() == len => Some(Or { or }),
                     _ => None,
                 },
+                Cond(v) => Some(Cond(v)),
             }
         }
         mem::swap(
@@ -231,6 +320,7 @@ impl PrereqTree {
                 Only(v) => Some(Only(v)),
                 And { and } => Some(And { and: apply(and, code, tree) }),
                 Or { or } => Some(Or { or: apply(or, code, tree) }),
+                Cond(v) => Some(Cond(v)),
             }
         }
         mem::swap(
@@ -276,5 +366,13 @@ impl PrereqTree {
     }
 }
 
+/// Total cost of the modules in a path.
+fn total_cost<F>(path: &[String], cost: &F) -> f32
+where
+    F: Fn(&str) -> Option<f32>,
+{
+    path.iter().filter_map(|v| cost(v)).sum()
+}
+
 #[cfg(test)]
 mod spec;
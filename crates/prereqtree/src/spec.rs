@@ -383,3 +383,86 @@ fn normalize_test() {
     //     )
     // );
 }
+
+#[test]
+fn min_path_by_test() {
+    // B is preferred over A, and C is never taken
+    let cost = |code: &str| match code {
+        "A" => Some(2.0),
+        "C" => None,
+        _ => Some(1.0),
+    };
+    let tree = t!(or, t!(A), t!(B));
+    assert_eq!(tree.min_path_by(&cost), Some(s_vec(vec!["B"])));
+    let tree = t!(or, t!(and, t!(A), t!(D)), t!(C));
+    let received = tree.min_path_by(&cost).unwrap();
+    assert!(vec_eq(&received, &s_vec(vec!["A", "D"]), |a, b| a.eq(b)));
+    assert_eq!(t!(and, t!(A), t!(C)).min_path_by(&cost), None);
+
+    let tree = t!(or, t!(and, t!(A), t!(D)), t!(and, t!(B), t!(D)), t!(C));
+    let filter = s_vec(vec!["D"]);
+    let received = tree.min_path_filtered_by(&filter, &cost).unwrap();
+    assert!(vec_eq(&received, &s_vec(vec!["B", "D"]), |a, b| a.eq(b)));
+    let filter = s_vec(vec!["C"]);
+    assert_eq!(tree.min_path_filtered_by(&filter, &cost), None);
+
+    // E and F are preferred enough to beat the single, cheaper-looking A
+    let prefer = |code: &str| match code {
+        "E" | "F" => Some(0.25),
+        "C" => None,
+        _ => Some(1.0),
+    };
+    let tree = t!(or, t!(A), t!(and, t!(E), t!(F)), t!(C));
+    let received = tree.min_path_by(&prefer).unwrap();
+    assert!(vec_eq(&received, &s_vec(vec!["E", "F"]), |a, b| a.eq(b)));
+    let received = tree.min_path_filtered_by(&vec![], &prefer).unwrap();
+    assert!(vec_eq(&received, &s_vec(vec!["E", "F"]), |a, b| a.eq(b)));
+}
+
+#[test]
+fn condition_test() {
+    use crate::{Condition, Profile};
+    let tree: PrereqTree =
+        serde_json::from_str(r#"{ "and": ["A", { "year": 3 }] }"#).unwrap();
+    assert_eq!(tree, t!(and, t!(A), PrereqTree::Cond(Condition::Year(3))));
+    let mut profile = Profile::default();
+    // conditions are only checked against a student
+    assert!(tree.satisfied_by(&done!(A)));
+    assert!(!tree.satisfied_with(&done!(A), &profile));
+    profile.year = 3;
+    assert!(tree.satisfied_with(&done!(A), &profile));
+    assert!(!tree.satisfied_with(&done!(), &profile));
+    assert_eq!(tree.min_path(), s_vec(vec!["A"]));
+
+    let tree: PrereqTree = serde_json::from_str(
+        r#"{ "or": [{ "credits": 80 }, { "programme": ["CS"] }] }"#,
+    )
+    .unwrap();
+    // a student of no known programme isn't held to programme restrictions
+    assert!(tree.satisfied_with(&done!(), &profile));
+    profile.programmes = s_vec(vec!["IS"]);
+    assert!(!tree.satisfied_with(&done!(), &profile));
+    profile.programmes = s_vec(vec!["CS"]);
+    assert!(tree.satisfied_with(&done!(), &profile));
+
+    let text = "CS2040 and Year 3 standing";
+    assert_eq!(Condition::from_text(text), vec![Condition::Year(3)]);
+    let text = "Completed 80 MCs, including CS2103/T";
+    assert_eq!(Condition::from_text(text), vec![Condition::Credits(80.0)]);
+    let text = "Students must have completed at least 112MCs.";
+    assert_eq!(Condition::from_text(text), vec![Condition::Credits(112.0)]);
+    // a module's own size is not a condition
+    assert_eq!(Condition::from_text("Any 4 MC module in CS"), vec![]);
+
+    let programme = |v: Vec<&str>| vec![Condition::Programme(s_vec(v))];
+    let text = "This module is only for Computer Science students.";
+    assert_eq!(Condition::from_text(text), programme(vec!["Computer Science"]));
+    let text = "CS2040 and only for students in Business Analytics or \
+                Information Systems";
+    assert_eq!(
+        Condition::from_text(text),
+        programme(vec!["Business Analytics", "Information Systems"])
+    );
+    let text = "Only for students who have completed 40 units";
+    assert_eq!(Condition::from_text(text), vec![Condition::Credits(40.0)]);
+}
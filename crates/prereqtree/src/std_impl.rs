@@ -27,6 +27,7 @@ impl fmt::Debug for PrereqTree {
             Self::Only(v) => write!(f, "{v}"),
             Self::Or { or: t } => write!(f, "OR {t:?}"),
             Self::And { and: t } => write!(f, "AND {t:?}"),
+            Self::Cond(v) => write!(f, "{v:?}"),
         }
     }
 }
@@ -53,6 +54,7 @@ impl PartialEq for PrereqTree {
             (Or { or: x }, Or { or: y }) => vec_eq(x, y, |a, b| a.eq(b)),
             (And { and: x }, And { and: y }) => vec_eq(x, y, |a, b| a.eq(b)),
             (Only(x), Only(y)) => x.eq(y),
+            (Cond(x), Cond(y)) => x.eq(y),
             _ => false,
         }
     }
@@ -0,0 +1,198 @@
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// An academic year, such as AY2022/2023. Reads both "2022/2023" and the
+/// "2022-2023" used in NUSMods URLs, and always writes "2022/2023".
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+#[serde(try_from = "String", into = "String")]
+pub struct AcademicYear {
+    start: u16,
+}
+
+impl AcademicYear {
+    /// The academic year that starts in `start`, or an error if the year it
+    /// ends in doesn't fit in a `u16`.
+    pub fn new(start: u16) -> Result<Self> {
+        match start.checked_add(1) {
+            Some(_) => Ok(Self { start }),
+            None => Err(Error::InvalidData(format!("overflow: {start}"))),
+        }
+    }
+
+    /// Calendar year that the academic year starts in.
+    pub fn start(&self) -> u16 {
+        self.start
+    }
+
+    /// The academic year after this one, if its years still fit in a
+    /// `u16`.
+    pub fn next(&self) -> Option<Self> {
+        Self::new(self.start.checked_add(1)?).ok()
+    }
+
+    /// The academic year before this one, if there is one.
+    pub fn prev(&self) -> Option<Self> {
+        Self::new(self.start.checked_sub(1)?).ok()
+    }
+
+    /// The academic year as NUSMods writes it in URLs, where '/' would mean
+    /// something else: "2022-2023".
+    pub fn to_url(&self) -> String {
+        format!("{}-{}", self.start, self.start + 1)
+    }
+}
+
+impl FromStr for AcademicYear {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self> {
+        let invalid = || Error::InvalidData(format!("academic year: {text}"));
+        let (start, end) = text.split_once(['/', '-']).ok_or_else(invalid)?;
+        let year = Self::new(start.parse::<u16>()?)?;
+        match end.parse::<u16>()? == year.start + 1 {
+            true => Ok(year),
+            false => Err(Error::InvalidData(format!(
+                "years should be consecutive: {text}"
+            ))),
+        }
+    }
+}
+
+impl TryFrom<String> for AcademicYear {
+    type Error = Error;
+
+    fn try_from(text: String) -> Result<Self> {
+        text.parse()
+    }
+}
+
+impl From<AcademicYear> for String {
+    fn from(year: AcademicYear) -> Self {
+        year.to_string()
+    }
+}
+
+impl fmt::Display for AcademicYear {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.start, self.start + 1)
+    }
+}
+
+/// A semester of an academic year. 1 and 2 are the regular semesters, and 3
+/// and 4 are the special terms.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+#[serde(try_from = "usize", into = "usize")]
+pub struct Semester(u8);
+
+impl Semester {
+    pub fn new(number: usize) -> Result<Self> {
+        match number {
+            1..=4 => Ok(Self(number as u8)),
+            _ => Err(Error::InvalidSemester),
+        }
+    }
+
+    /// Semester of an index into `Path::record`, where every academic year
+    /// has four semesters.
+    pub fn of_index(index: usize) -> Self {
+        Self((index % 4 + 1) as u8)
+    }
+
+    /// Number of the semester, from 1 to 4.
+    pub fn number(&self) -> usize {
+        self.0 as usize
+    }
+
+    /// The semester after this one, going on to the next academic year
+    /// after the last special term.
+    pub fn next(&self) -> Self {
+        Self(self.0 % 4 + 1)
+    }
+
+    /// The semester before this one, going back to the last academic year
+    /// before the first semester.
+    pub fn prev(&self) -> Self {
+        Self((self.0 + 2) % 4 + 1)
+    }
+}
+
+impl TryFrom<usize> for Semester {
+    type Error = Error;
+
+    fn try_from(number: usize) -> Result<Self> {
+        Self::new(number)
+    }
+}
+
+impl From<Semester> for usize {
+    fn from(sem: Semester) -> Self {
+        sem.number()
+    }
+}
+
+impl fmt::Display for Semester {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[test]
+fn academic_year_test() {
+    let year: AcademicYear = "2022/2023".parse().unwrap();
+    assert_eq!("2022-2023".parse::<AcademicYear>().unwrap(), year);
+    assert_eq!(year.to_string(), "2022/2023");
+    assert_eq!(year.to_url(), "2022-2023");
+    assert!(year.prev().unwrap() < year && Some(year) < year.next());
+    assert_eq!(year.next().unwrap().to_string(), "2023/2024");
+    assert_eq!(AcademicYear::new(0).unwrap().prev(), None);
+    // the last year that ends within a `u16`
+    let last = AcademicYear::new(u16::MAX - 1).unwrap();
+    assert_eq!(last.to_string(), "65534/65535");
+    assert_eq!(last.to_url(), "65534-65535");
+    assert_eq!(last.next(), None);
+    assert!(AcademicYear::new(u16::MAX).is_err());
+    assert!("65535/65536".parse::<AcademicYear>().is_err());
+    for bad in ["2022", "2022/2024", "AY2022/2023", "2022_2023"] {
+        assert!(bad.parse::<AcademicYear>().is_err(), "{bad}");
+    }
+    let json = serde_json::to_string(&year).unwrap();
+    assert_eq!(json, r#""2022/2023""#);
+    let parsed: AcademicYear = serde_json::from_str(r#""2022-2023""#).unwrap();
+    assert_eq!(parsed, year);
+}
+
+#[test]
+fn semester_test() {
+    let sem = Semester::new(1).unwrap();
+    assert_eq!(sem.next(), Semester::new(2).unwrap());
+    assert_eq!(sem.prev(), Semester::new(4).unwrap());
+    assert_eq!(Semester::new(4).unwrap().next(), sem);
+    assert_eq!(Semester::of_index(9), Semester::new(2).unwrap());
+    assert!(Semester::new(0).is_err() && Semester::new(5).is_err());
+    assert_eq!(serde_json::to_string(&sem).unwrap(), "1");
+    assert!(serde_json::from_str::<Semester>("5").is_err());
+}
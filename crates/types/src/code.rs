@@ -0,0 +1,119 @@
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// A module code, such as `CS2030S`: a subject prefix of 2 to 4 letters, a
+/// 4-digit number, and a variant suffix of up to 3 letters. Codes are
+/// ordered by prefix, then number, then suffix.
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(try_from = "String", into = "String")]
+pub struct ModuleCode {
+    prefix: String,
+    number: u16,
+    suffix: String,
+}
+
+impl ModuleCode {
+    /// Subject prefix, such as "CS".
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Number after the prefix, such as 2030.
+    pub fn number(&self) -> u16 {
+        self.number
+    }
+
+    /// Level of the module, such as 2000 for CS2030S.
+    pub fn level(&self) -> u16 {
+        self.number / 1000 * 1000
+    }
+
+    /// Variant suffix, such as "S", or "" if there is none.
+    pub fn suffix(&self) -> &str {
+        &self.suffix
+    }
+
+    /// Checks if two codes are variants of the same module, such as CS1010
+    /// and CS1010S.
+    pub fn same_base(&self, other: &ModuleCode) -> bool {
+        self.prefix == other.prefix && self.number == other.number
+    }
+}
+
+impl FromStr for ModuleCode {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self> {
+        let invalid = || Error::InvalidModuleCode(text.to_string());
+        let is_letters = |s: &str| s.chars().all(|c| c.is_ascii_uppercase());
+        let digits = text.find(|c: char| c.is_ascii_digit());
+        let (prefix, rest) = text.split_at(digits.ok_or_else(invalid)?);
+        if rest.len() < 4 || !rest.is_char_boundary(4) {
+            return Err(invalid());
+        }
+        let (number, suffix) = rest.split_at(4);
+        if !(2..=4).contains(&prefix.len())
+            || !is_letters(prefix)
+            || !number.chars().all(|c| c.is_ascii_digit())
+            || suffix.len() > 3
+            || !is_letters(suffix)
+        {
+            return Err(invalid());
+        }
+        Ok(Self {
+            prefix: prefix.to_string(),
+            number: number.parse()?,
+            suffix: suffix.to_string(),
+        })
+    }
+}
+
+impl TryFrom<String> for ModuleCode {
+    type Error = Error;
+
+    fn try_from(text: String) -> Result<Self> {
+        text.parse()
+    }
+}
+
+impl From<ModuleCode> for String {
+    fn from(code: ModuleCode) -> Self {
+        code.to_string()
+    }
+}
+
+impl fmt::Display for ModuleCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{:04}{}", self.prefix, self.number, self.suffix)
+    }
+}
+
+#[test]
+fn parse_test() {
+    let code: ModuleCode = "CS2030S".parse().unwrap();
+    assert_eq!(
+        (code.prefix(), code.number(), code.suffix()),
+        ("CS", 2030, "S")
+    );
+    assert_eq!(code.level(), 2000);
+    assert_eq!(code.to_string(), "CS2030S");
+    assert!(code.same_base(&"CS2030".parse().unwrap()));
+    for good in ["MA1521", "GESS1000", "LAJ1201", "CS1010XCP"] {
+        assert_eq!(good.parse::<ModuleCode>().unwrap().to_string(), good);
+    }
+    for bad in ["", "A", "CS", "C2030", "CS203", "CS20301", "cs2030", "CS2030s"]
+    {
+        assert!(bad.parse::<ModuleCode>().is_err(), "{bad}");
+    }
+    let mut codes: Vec<ModuleCode> = ["CS2030S", "MA1521", "CS2030", "CS1010"]
+        .iter()
+        .map(|c| c.parse().unwrap())
+        .collect();
+    codes.sort();
+    let codes: Vec<_> = codes.iter().map(|c| c.to_string()).collect();
+    assert_eq!(codes, ["CS1010", "CS2030", "CS2030S", "MA1521"]);
+}
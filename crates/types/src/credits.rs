@@ -0,0 +1,63 @@
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Modular credits (MCs) of a module. Most modules have a fixed number of
+/// credits, where `min` and `max` are equal, but some are worth a range of
+/// credits depending on how they are taken.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct Credits {
+    pub min: f32,
+    pub max: f32,
+}
+
+impl Credits {
+    pub fn fixed(value: f32) -> Self {
+        Self { min: value, max: value }
+    }
+
+    /// Checks if the module is worth a fixed number of credits.
+    pub fn is_fixed(&self) -> bool {
+        self.min == self.max
+    }
+
+    /// Reads NUSMods' `moduleCredit`, such as "4", "2.5" or "4-8".
+    pub fn parse(text: &str) -> Result<Self> {
+        let number = |v: &str| match v.trim().parse::<f32>() {
+            Ok(v) if v.is_finite() && v >= 0.0 => Ok(v),
+            _ => Err(Error::InvalidCredits(text.to_string())),
+        };
+        match text.split_once(['-', '–']) {
+            Some((min, max)) => {
+                let (min, max) = (number(min)?, number(max)?);
+                match min <= max {
+                    true => Ok(Self { min, max }),
+                    false => Err(Error::InvalidCredits(text.to_string())),
+                }
+            }
+            None => number(text).map(Self::fixed),
+        }
+    }
+}
+
+/// Writes the credits the way NUSMods does, such as "4" or "4-8".
+impl fmt::Display for Credits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.is_fixed() {
+            true => write!(f, "{}", self.min),
+            false => write!(f, "{}-{}", self.min, self.max),
+        }
+    }
+}
+
+#[test]
+fn parse_test() {
+    assert_eq!(Credits::parse("4").unwrap(), Credits::fixed(4.0));
+    assert_eq!(Credits::parse(" 2.5 ").unwrap(), Credits::fixed(2.5));
+    let range = Credits::parse("4-8").unwrap();
+    assert_eq!(range, Credits { min: 4.0, max: 8.0 });
+    assert_eq!(range.to_string(), "4-8");
+    for bad in ["", "four", "8-4", "-4", "NaN"] {
+        assert!(Credits::parse(bad).is_err(), "{bad}");
+    }
+}
@@ -27,6 +27,9 @@ pub enum Error {
     #[error("Module not found: {0}, AY{1}")]
     ModuleNotFound(String, String),
 
+    #[error("Requirements not found: {0}, matriculated AY{1}")]
+    RequirementsNotFound(String, String),
+
     #[error("Semesters not found: {0}")]
     ModuleSemestersNotFound(String),
 
@@ -57,9 +60,21 @@ pub enum Error {
     #[error("Unable to parse float: {0:#?}")]
     ParseFloatError(std::num::ParseFloatError),
 
+    #[error("Solver failed: {0}")]
+    SolverFailed(String),
+
     #[error("Tried to delete a core database: {0}")]
     MongoDbBadDrop(String),
 
+    #[error("Invalid module code: {0:?}")]
+    InvalidModuleCode(String),
+
+    #[error("Invalid modular credits: {0:?}")]
+    InvalidCredits(String),
+
+    #[error("Unreadable academic year of module: {0}")]
+    InvalidAcademicYear(String),
+
     #[error("Invalid semester array")]
     InvalidSemesters(Vec<usize>),
 
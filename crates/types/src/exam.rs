@@ -0,0 +1,64 @@
+use crate::Semester;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Final exam of a module in one semester.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Exam {
+    pub semester: Semester,
+    /// Start of the exam in RFC 3339, as given by NUSMods.
+    pub date: String,
+    /// Length of the exam in minutes.
+    pub duration: u32,
+}
+
+impl Exam {
+    /// Start of the exam, if the date can be read.
+    pub fn start(&self) -> Option<DateTime<Utc>> {
+        let date = DateTime::parse_from_rfc3339(&self.date).ok()?;
+        Some(date.with_timezone(&Utc))
+    }
+
+    /// End of the exam, if the date can be read.
+    pub fn end(&self) -> Option<DateTime<Utc>> {
+        Some(self.start()? + Duration::minutes(self.duration as i64))
+    }
+
+    /// Checks if two exams in the same semester overlap in time. Exams
+    /// whose dates can't be read never clash.
+    pub fn clashes_with(&self, other: &Exam) -> bool {
+        let (Some(start), Some(end)) = (self.start(), self.end()) else {
+            return false;
+        };
+        let (Some(other_start), Some(other_end)) = (other.start(), other.end())
+        else {
+            return false;
+        };
+        self.semester == other.semester
+            && start < other_end
+            && other_start < end
+    }
+
+    /// Reads the exam from one semester of NUSMods data, if it has one.
+    pub(crate) fn from_semester(s: &nusmods::SemesterData) -> Option<Self> {
+        Some(Self {
+            semester: Semester::new(s.semester).ok()?,
+            date: s.exam_date.clone()?,
+            duration: s.exam_duration.unwrap_or(120),
+        })
+    }
+}
+
+#[test]
+fn clash_test() {
+    let exam = |sem, date: &str, duration| Exam {
+        semester: Semester::new(sem).unwrap(),
+        date: date.to_string(),
+        duration,
+    };
+    let a = exam(1, "2022-11-23T01:00:00.000Z", 120);
+    assert!(a.clashes_with(&exam(1, "2022-11-23T02:30:00.000Z", 60)));
+    assert!(!a.clashes_with(&exam(1, "2022-11-23T03:00:00.000Z", 60)));
+    assert!(!a.clashes_with(&exam(2, "2022-11-23T01:00:00.000Z", 120)));
+    assert!(!a.clashes_with(&exam(1, "TBA", 120)));
+}
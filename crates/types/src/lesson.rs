@@ -0,0 +1,209 @@
+use crate::Semester;
+use serde::{Deserialize, Serialize};
+
+/// Weeks of the semester that a lesson runs in.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum Weeks {
+    Numbers(Vec<usize>),
+    /// Dates of the first and last lesson, such as "2023-01-16". The lesson
+    /// meets every `week_interval` weeks from `start`, or only in `weeks`,
+    /// counted from 1 for the week of `start`, if they are given.
+    Range {
+        start: String,
+        end: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        week_interval: Option<usize>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        weeks: Option<Vec<usize>>,
+    },
+}
+
+impl Weeks {
+    /// Checks if two lessons could meet in the same week. Weeks given in
+    /// different forms, or with dates that can't be read, are assumed to
+    /// overlap.
+    pub fn overlaps(&self, other: &Weeks) -> bool {
+        use Weeks::*;
+        match (self, other) {
+            (Numbers(a), Numbers(b)) => a.iter().any(|w| b.contains(w)),
+            (Range { .. }, Range { .. }) => {
+                match (self.calendar_weeks(), other.calendar_weeks()) {
+                    (Some(a), Some(b)) => a.iter().any(|w| b.contains(w)),
+                    _ => true,
+                }
+            }
+            _ => true,
+        }
+    }
+
+    /// Weeks in which a lesson given as a range of dates meets, each as the
+    /// number of weeks from a fixed Monday. `None` if the weeks are given as
+    /// numbers, or the dates can't be read.
+    fn calendar_weeks(&self) -> Option<Vec<i64>> {
+        let Weeks::Range { start, end, week_interval, weeks } = self else {
+            return None;
+        };
+        let (start, end) = (days(start)?, days(end)?);
+        let offsets: Vec<i64> = match weeks {
+            Some(weeks) => weeks.iter().map(|w| (*w as i64 - 1) * 7).collect(),
+            None => {
+                let step = 7 * week_interval.unwrap_or(1).max(1) as i64;
+                let offsets = (0..).map(|k| k * step);
+                offsets.take_while(|d| start + d <= end).collect()
+            }
+        };
+        // day 0 is a Thursday, so weeks start on Mondays
+        Some(offsets.iter().map(|d| (start + d + 3).div_euclid(7)).collect())
+    }
+}
+
+/// Number of days from 1970-01-01 to a date written as "2023-01-16".
+fn days(date: &str) -> Option<i64> {
+    let mut parts = date.get(..10)?.split('-').map(|v| v.parse::<i64>().ok());
+    let (y, m, d) = (parts.next()??, parts.next()??, parts.next()??);
+    if !(1..=12).contains(&m) || !(1..=31).contains(&d) {
+        return None;
+    }
+    // years counted from March, so that leap days come last
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let year = y - era * 400;
+    let day = (153 * ((m + 9) % 12) + 2) / 5 + d - 1;
+    let day = year * 365 + year / 4 - year / 100 + day;
+    Some(era * 146097 + day - 719468)
+}
+
+/// One weekly class of a module in one semester. A student attends every
+/// lesson with the same class number, for each lesson type.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Lesson {
+    pub semester: Semester,
+    pub class_no: String,
+    /// Such as "Lecture" or "Tutorial".
+    pub lesson_type: String,
+    pub day: String,
+    /// Time of day, such as "1000".
+    pub start_time: String,
+    pub end_time: String,
+    pub weeks: Weeks,
+}
+
+impl Lesson {
+    /// Reads the lessons from one semester of NUSMods data.
+    /// Lessons in a semester outside of 1 to 4 are left out.
+    pub(crate) fn from_semester(s: &nusmods::SemesterData) -> Vec<Self> {
+        let Ok(semester) = Semester::new(s.semester) else {
+            return vec![];
+        };
+        let lesson = |l: &nusmods::Lesson| Self {
+            semester,
+            class_no: l.class_no.clone(),
+            lesson_type: l.lesson_type.clone(),
+            day: l.day.clone(),
+            start_time: l.start_time.clone(),
+            end_time: l.end_time.clone(),
+            weeks: match &l.weeks {
+                nusmods::Weeks::Numbers(v) => Weeks::Numbers(v.clone()),
+                nusmods::Weeks::Range { start, end, week_interval, weeks } => {
+                    Weeks::Range {
+                        start: start.clone(),
+                        end: end.clone(),
+                        week_interval: *week_interval,
+                        weeks: weeks.clone(),
+                    }
+                }
+            },
+        };
+        s.timetable.iter().map(lesson).collect()
+    }
+
+    /// Checks if two lessons meet at the same time. Times are 24-hour
+    /// "HHMM" strings, so they can be compared as text.
+    pub fn clashes_with(&self, other: &Lesson) -> bool {
+        self.semester == other.semester
+            && self.day == other.day
+            && self.start_time < other.end_time
+            && other.start_time < self.end_time
+            && self.weeks.overlaps(&other.weeks)
+    }
+}
+
+#[test]
+fn lesson_test() {
+    let json = r#"{
+        "semester": 1,
+        "timetable": [{
+            "classNo": "1",
+            "startTime": "1000",
+            "endTime": "1200",
+            "weeks": { "start": "2023-01-16", "end": "2023-04-14" },
+            "venue": "LT19",
+            "day": "Monday",
+            "lessonType": "Lecture",
+            "size": 300
+        }]
+    }"#;
+    let data: nusmods::SemesterData = serde_json::from_str(json).unwrap();
+    let lecture = &Lesson::from_semester(&data)[0];
+    let lab = Lesson {
+        lesson_type: "Laboratory".to_string(),
+        start_time: "1100".to_string(),
+        end_time: "1300".to_string(),
+        weeks: Weeks::Numbers(vec![3, 5, 7]),
+        ..lecture.clone()
+    };
+    assert!(lecture.clashes_with(&lab));
+    let tutorial = Lesson { start_time: "1200".to_string(), ..lab.clone() };
+    assert!(!lecture.clashes_with(&tutorial));
+    let other_weeks =
+        Lesson { weeks: Weeks::Numbers(vec![4, 6]), ..lab.clone() };
+    assert!(!lab.clashes_with(&other_weeks));
+}
+
+#[test]
+fn weeks_test() {
+    assert_eq!(days("1970-01-01"), Some(0));
+    assert_eq!(days("2024-03-01"), Some(19783));
+    assert_eq!(days("2023-13-01"), None);
+    let range =
+        |start: &str, interval, weeks: Option<Vec<usize>>| Weeks::Range {
+            start: start.to_string(),
+            end: "2023-04-14".to_string(),
+            week_interval: interval,
+            weeks,
+        };
+    // tutorials in the same slot on odd and even weeks don't clash
+    let odd = range("2023-01-16", Some(2), None);
+    let even = range("2023-01-23", Some(2), None);
+    assert!(!odd.overlaps(&even));
+    assert!(odd.overlaps(&range("2023-01-16", None, None)));
+    let odd = range("2023-01-16", None, Some(vec![1, 3, 5]));
+    let even = range("2023-01-16", None, Some(vec![2, 4, 6]));
+    assert!(!odd.overlaps(&even));
+    assert!(odd.overlaps(&range("2023-01-30", Some(2), None)));
+    assert!(odd.overlaps(&range("someday", None, None)));
+
+    let json = r#"{
+        "semester": 2,
+        "timetable": [{
+            "classNo": "01",
+            "startTime": "1400",
+            "endTime": "1600",
+            "weeks": { "start": "2023-01-16", "end": "2023-04-14", "weekInterval": 2 },
+            "day": "Friday",
+            "lessonType": "Tutorial"
+        }, {
+            "classNo": "02",
+            "startTime": "1400",
+            "endTime": "1600",
+            "weeks": { "start": "2023-01-23", "end": "2023-04-14", "weekInterval": 2 },
+            "day": "Friday",
+            "lessonType": "Tutorial"
+        }]
+    }"#;
+    let data: nusmods::SemesterData = serde_json::from_str(json).unwrap();
+    let lessons = Lesson::from_semester(&data);
+    assert_eq!(lessons[0].weeks, range("2023-01-16", Some(2), None));
+    assert!(!lessons[0].clashes_with(&lessons[1]));
+}
@@ -1,30 +1,18 @@
-use serde::{Deserialize, Serialize};
-
+mod academic;
+mod code;
+mod credits;
 mod errors;
+mod exam;
+mod lesson;
 mod module;
+mod workload;
+pub use academic::{AcademicYear, Semester};
+pub use code::ModuleCode;
+pub use credits::Credits;
 pub use errors::*;
+pub use exam::Exam;
+pub use lesson::{Lesson, Weeks};
 pub use module::Module;
+pub use workload::Workload;
 
 pub type Result<T> = std::result::Result<T, errors::Error>;
-
-#[derive(Debug, Serialize, Deserialize, Clone)]
-#[serde(untagged)]
-pub(crate) enum Workload {
-    String(String),
-    Numbers(Vec<f32>),
-}
-
-impl Default for Workload {
-    fn default() -> Self {
-        Self::Numbers(vec![])
-    }
-}
-
-impl From<nusmods::Workload> for Workload {
-    fn from(w: nusmods::Workload) -> Self {
-        match w {
-            nusmods::Workload::String(s) => Self::String(s),
-            nusmods::Workload::Numbers(v) => Self::Numbers(v),
-        }
-    }
-}
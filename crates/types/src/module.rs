@@ -1,6 +1,11 @@
-use crate::{Workload};
+use crate::workload::RawWorkload;
+use crate::{
+    AcademicYear, Credits, Error, Exam, Lesson, ModuleCode, Result, Semester,
+    Workload,
+};
 use bson::oid::ObjectId;
-use prereqtree::PrereqTree;
+use nusmods::Attributes;
+use prereqtree::{Condition, PrereqTree};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::hash::{Hash, Hasher};
@@ -8,28 +13,49 @@ use std::hash::{Hash, Hasher};
 /// `modtree` edition of a module
 #[derive(Serialize, Deserialize, Default, Clone)]
 pub struct Module {
-    acad_year: String,
+    /// `None` if NUSMods gives an academic year that can't be read.
+    acad_year: Option<AcademicYear>,
     preclusion: String,
     description: String,
     title: String,
     department: String,
     faculty: String,
     prerequisite: String,
+    #[serde(default)]
+    corequisite: String,
     module_credit: String,
+    /// `module_credit` as read at conversion, if it could be read.
+    #[serde(default)]
+    credits: Option<Credits>,
     module_code: String,
     fulfill_requirements: Vec<String>,
     prereqtree: PrereqTree,
-    workload: Workload,
+    workload: RawWorkload,
+    #[serde(default)]
+    exams: Vec<Exam>,
+    #[serde(default)]
+    lessons: Vec<Lesson>,
+    #[serde(default)]
+    attributes: Attributes,
+    #[serde(default)]
+    grading_basis: Option<String>,
+    #[serde(default)]
+    prereq_rule: Option<String>,
+    #[serde(default)]
+    aliases: Vec<String>,
+    #[serde(default)]
+    additional_information: Option<String>,
     // extra stuff on top of standard NUSMods API
-    semesters: Vec<usize>,
+    semesters: Vec<Semester>,
     #[serde(skip_serializing_if = "Option::is_none")]
     _id: Option<ObjectId>,
 }
 
 impl Module {
-    /// Gets a reference to the module's academic year
-    pub fn acad_year(&self) -> &String {
-        &self.acad_year
+    /// Gets the module's academic year, or an error if NUSMods gives one
+    /// that cannot be read.
+    pub fn acad_year(&self) -> Result<AcademicYear> {
+        self.acad_year.ok_or_else(|| Error::InvalidAcademicYear(self.to_code()))
     }
 
     /// Gets a reference to the module's code
@@ -42,13 +68,119 @@ impl Module {
         self.module_code.to_string()
     }
 
+    /// Parses the module's code into its prefix, number and suffix
+    pub fn module_code(&self) -> Result<ModuleCode> {
+        self.module_code.parse()
+    }
+
+    /// Gets the text of the module's corequisites
+    pub fn corequisite(&self) -> &String {
+        &self.corequisite
+    }
+
+    /// Gets the module's flags, such as whether it has an S/U option
+    pub fn attributes(&self) -> &Attributes {
+        &self.attributes
+    }
+
+    /// Checks if undergraduates can S/U the module
+    pub fn can_su(&self) -> bool {
+        self.attributes.su.unwrap_or(false)
+    }
+
+    /// Gets how the module is graded, such as "Letter Grade", if known
+    pub fn grading_basis(&self) -> Option<&String> {
+        self.grading_basis.as_ref()
+    }
+
+    /// Gets the prerequisite rule that the prereqtree is built from, if
+    /// NUSMods gives one
+    pub fn prereq_rule(&self) -> Option<&String> {
+        self.prereq_rule.as_ref()
+    }
+
+    /// Gets the other codes that the module is listed under
+    pub fn aliases(&self) -> &Vec<String> {
+        &self.aliases
+    }
+
+    /// Gets any notes on the module beyond its description, if NUSMods
+    /// gives them
+    pub fn additional_information(&self) -> Option<&String> {
+        self.additional_information.as_ref()
+    }
+
+    /// Gets the module's modular credits, or an error if NUSMods gives them
+    /// in a form that cannot be read. Modules stored before `credits` was
+    /// kept have their `module_credit` read instead.
+    pub fn credits(&self) -> Result<Credits> {
+        match self.credits {
+            Some(credits) => Ok(credits),
+            None => Credits::parse(&self.module_credit).map_err(|_| {
+                Error::InvalidCredits(format!(
+                    "{}: {}",
+                    self.module_code, self.module_credit
+                ))
+            }),
+        }
+    }
+
+    /// Sets the modular credits
+    pub fn set_credits(&mut self, credits: Credits) {
+        self.module_credit = credits.to_string();
+        self.credits = Some(credits);
+    }
+
+    /// Gets the module's weekly workload, or `None` if NUSMods describes it
+    /// in words that can't be read.
+    pub fn workload(&self) -> Option<Workload> {
+        self.workload.parse()
+    }
+
+    /// Gets the module's total weekly workload in hours, or 0 if it can't
+    /// be read.
+    pub fn workload_hours(&self) -> f32 {
+        self.workload().map_or(0.0, |w| w.total())
+    }
+
+    /// Gets the codes of modules that preclude this one. NUSMods only gives
+    /// preclusions as text, so this picks out every word that reads as a
+    /// `ModuleCode`, such as `CS1010` or `CS1010S`.
+    pub fn preclusions(&self) -> Vec<String> {
+        let words = self.preclusion.split(|c: char| !c.is_ascii_alphanumeric());
+        words
+            .filter(|w| w.parse::<ModuleCode>().is_ok())
+            .filter(|w| *w != self.module_code)
+            .map(|w| w.to_string())
+            .collect()
+    }
+
+    /// Gets the module's exam in a semester, if it has one.
+    pub fn exam(&self, sem: Semester) -> Option<&Exam> {
+        self.exams.iter().find(|e| e.semester == sem)
+    }
+
+    /// Checks if the module's exam in a semester overlaps with another
+    /// module's exam in the same semester.
+    pub fn exam_clashes_with(&self, other: &Module, sem: Semester) -> bool {
+        match (self.exam(sem), other.exam(sem)) {
+            (Some(a), Some(b)) => a.clashes_with(b),
+            _ => false,
+        }
+    }
+
+    /// Gets the module's lessons in a semester.
+    pub fn lessons(&self, sem: Semester) -> Vec<&Lesson> {
+        self.lessons.iter().filter(|l| l.semester == sem).collect()
+    }
+
     /// Gets a reference to the module's semester list
-    pub fn semesters(&self) -> &Vec<usize> {
+    pub fn semesters(&self) -> &Vec<Semester> {
         &self.semesters
     }
 
     /// Clones the module's semester list
-    pub fn to_semesters(&self) -> Vec<usize> {
+    pub fn to_semesters(&self) -> Vec<Semester> {
         self.semesters.clone()
     }
 
@@ -73,9 +205,19 @@ impl Module {
     }
 
     /// Sets the semesters
-    pub fn set_semesters(&mut self, sems: Vec<usize>) {
+    pub fn set_semesters(&mut self, sems: Vec<Semester>) {
         self.semesters = sems;
     }
+
+    /// Sets the exams
+    pub fn set_exams(&mut self, exams: Vec<Exam>) {
+        self.exams = exams;
+    }
+
+    /// Sets the lessons
+    pub fn set_lessons(&mut self, lessons: Vec<Lesson>) {
+        self.lessons = lessons;
+    }
 }
 
 impl PartialEq for Module {
@@ -103,21 +245,58 @@ impl fmt::Debug for Module {
 
 impl From<nusmods::Module> for Module {
     fn from(m: nusmods::Module) -> Self {
+        // NUSMods only gives year standing in the text of the prerequisites
+        let mut prereqtree = PrereqTree::from(m.prereqtree);
+        let conditions = Condition::from_text(&m.prerequisite);
+        if !conditions.is_empty() {
+            let mut and: Vec<_> =
+                conditions.into_iter().map(PrereqTree::Cond).collect();
+            if !prereqtree.is_empty() {
+                and.insert(0, prereqtree);
+            }
+            prereqtree = PrereqTree::And { and };
+        }
         Self {
             _id: None,
             semesters: vec![],
-            acad_year: m.acad_year,
+            acad_year: m.acad_year.parse().ok(),
             preclusion: m.preclusion,
             description: m.description,
             title: m.title,
             department: m.department,
             faculty: m.faculty,
             prerequisite: m.prerequisite,
+            corequisite: m.corequisite,
+            credits: Credits::parse(&m.module_credit).ok(),
             module_credit: m.module_credit,
             module_code: m.module_code,
             fulfill_requirements: m.fulfill_requirements,
-            workload: Workload::from(m.workload),
-            prereqtree: PrereqTree::from(m.prereqtree),
+            workload: RawWorkload::from(m.workload),
+            exams: m
+                .semester_data
+                .iter()
+                .filter_map(Exam::from_semester)
+                .collect(),
+            lessons: m
+                .semester_data
+                .iter()
+                .flat_map(Lesson::from_semester)
+                .collect(),
+            attributes: m.attributes,
+            grading_basis: m.grading_basis_description,
+            prereq_rule: m.prereq_rule,
+            aliases: m.aliases,
+            additional_information: m.additional_information,
+            prereqtree,
         }
     }
 }
+
+#[test]
+fn credits_test() {
+    // stored before `credits` was kept
+    let old = Module { module_credit: "4".to_string(), ..Module::default() };
+    assert_eq!(old.credits().unwrap(), Credits::fixed(4.0));
+    let bad = Module { module_credit: "four".to_string(), ..Module::default() };
+    assert!(bad.credits().is_err());
+}
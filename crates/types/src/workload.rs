@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+use std::iter::Sum;
+use std::ops::Add;
+
+/// Workload as NUSMods gives it: either five numbers, or text.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub(crate) enum RawWorkload {
+    String(String),
+    Numbers(Vec<f32>),
+}
+
+impl Default for RawWorkload {
+    fn default() -> Self {
+        Self::Numbers(vec![])
+    }
+}
+
+impl From<nusmods::Workload> for RawWorkload {
+    fn from(w: nusmods::Workload) -> Self {
+        match w {
+            nusmods::Workload::String(s) => Self::String(s),
+            nusmods::Workload::Numbers(v) => Self::Numbers(v),
+        }
+    }
+}
+
+/// Weekly workload of a module, in hours.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+pub struct Workload {
+    pub lecture: f32,
+    pub tutorial: f32,
+    pub lab: f32,
+    pub project: f32,
+    pub preparation: f32,
+}
+
+impl Workload {
+    /// Total hours per week.
+    pub fn total(&self) -> f32 {
+        self.lecture
+            + self.tutorial
+            + self.lab
+            + self.project
+            + self.preparation
+    }
+
+    /// Reads hours in NUSMods' order: lecture, tutorial, lab, project and
+    /// preparation. Any extra hours count as preparation.
+    pub fn from_numbers(v: &[f32]) -> Self {
+        let at = |i: usize| v.get(i).copied().unwrap_or(0.0);
+        let extra: f32 = v.iter().skip(5).sum();
+        Self {
+            lecture: at(0),
+            tutorial: at(1),
+            lab: at(2),
+            project: at(3),
+            preparation: at(4) + extra,
+        }
+    }
+
+    /// Best-effort reading of a workload written as text. Handles numbers
+    /// in NUSMods' order, such as "2-1-1-3-3", and labelled hours, such as
+    /// "Lecture: 3 hours, Tutorial: 1 hour". Returns `None` if no hours are
+    /// found.
+    pub fn parse(text: &str) -> Option<Self> {
+        let numbers: Option<Vec<f32>> = text
+            .split(['-', ','])
+            .map(|v| v.trim().parse::<f32>().ok())
+            .collect();
+        if let Some(numbers) = numbers.filter(|v| v.len() > 1) {
+            return Some(Self::from_numbers(&numbers));
+        }
+        // each clause gives the hours of at most one kind of class
+        let mut hours = [0.0; 5];
+        let mut found = false;
+        for clause in text.split([',', ';', '\n', '+']) {
+            let words: Vec<String> = clause
+                .split(|c: char| c.is_whitespace() || c == ':' || c == '/')
+                .map(|w| w.to_lowercase())
+                .collect();
+            let value = words.iter().find_map(|w| w.parse::<f32>().ok());
+            let kind = words.iter().find_map(|w| kind(w));
+            if let (Some(value), Some(kind)) = (value, kind) {
+                hours[kind] += value;
+                found = true;
+            }
+        }
+        found.then(|| Self::from_numbers(&hours))
+    }
+}
+
+/// Kind of class that a word names, as an index in NUSMods' order.
+fn kind(word: &str) -> Option<usize> {
+    let prefixes: [&[&str]; 5] = [
+        &["lec"],
+        &["tut", "semin"],
+        &["lab", "prac"],
+        &["proj", "assign"],
+        &["prep", "self"],
+    ];
+    prefixes.iter().position(|p| p.iter().any(|p| word.starts_with(p)))
+}
+
+impl Add for Workload {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            lecture: self.lecture + other.lecture,
+            tutorial: self.tutorial + other.tutorial,
+            lab: self.lab + other.lab,
+            project: self.project + other.project,
+            preparation: self.preparation + other.preparation,
+        }
+    }
+}
+
+impl Sum for Workload {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
+
+impl RawWorkload {
+    /// Reads the workload, if it can be read.
+    pub(crate) fn parse(&self) -> Option<Workload> {
+        match self {
+            Self::Numbers(v) => Some(Workload::from_numbers(v)),
+            Self::String(s) => Workload::parse(s),
+        }
+    }
+}
+
+#[test]
+fn parse_test() {
+    let workload = Workload::parse("2-1-1-3-3").unwrap();
+    assert_eq!(workload, Workload::from_numbers(&[2.0, 1.0, 1.0, 3.0, 3.0]));
+    assert_eq!(workload.total(), 10.0);
+
+    let text = "Lecture: 3 hours; Tutorial: 1 hour; Self-study: 6 hours";
+    let workload = Workload::parse(text).unwrap();
+    assert_eq!((workload.lecture, workload.tutorial), (3.0, 1.0));
+    assert_eq!(workload.preparation, 6.0);
+
+    let workload =
+        Workload::parse("2 hours of lectures, 2.5 hours lab").unwrap();
+    assert_eq!((workload.lecture, workload.lab), (2.0, 2.5));
+    assert_eq!(Workload::parse("Varies by project"), None);
+}